@@ -0,0 +1,799 @@
+use std::collections::HashMap;
+use std::fs::{self, read_dir, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::{thread, time};
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    terminal::Frame,
+    widgets::{Block, Cell, Row, Table, TableState},
+};
+
+use crate::disk::{self, MountInfo};
+use crate::util;
+
+const SYS: &str = "/sys";
+const PROC_MDSTAT: &str = "/proc/mdstat";
+const PROC_MOUNTINFO: &str = "/proc/self/mountinfo";
+
+/// The kernel reports block device sizes in units of 512 bytes
+const SECTOR_SIZE: u64 = 512;
+
+/// Maximum nesting of the tree, protects against loops in broken sysfs links
+const MAX_DEPTH: usize = 16;
+
+/// Kind of a block device, similar to the TYPE column of "lsblk"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeviceKind {
+    Disk,
+    Partition,
+    /// Logical volume of the LVM
+    Lvm,
+    /// Other device-mapper devices, e.g. dm-crypt
+    DeviceMapper,
+    /// Software RAID array
+    Raid,
+    Loop,
+}
+
+impl DeviceKind {
+    /// Returns the short name which is displayed in the table
+    pub fn get_name(&self) -> &str {
+        match *self {
+            DeviceKind::Disk => "disk",
+            DeviceKind::Partition => "part",
+            DeviceKind::Lvm => "lvm",
+            DeviceKind::DeviceMapper => "dm",
+            DeviceKind::Raid => "raid",
+            DeviceKind::Loop => "loop",
+        }
+    }
+}
+
+/// State of a software RAID array, read from /proc/mdstat
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RaidStatus {
+    /// e.g. "raid1"
+    pub level: String,
+    pub active: bool,
+    /// Working and expected members, e.g. "[2/1]"
+    pub members: String,
+    /// State of each member, "U" is up and "_" is missing, e.g. "[U_]"
+    pub health: String,
+    /// Running resync, recovery, reshape or check with its progress, e.g. "recovery 12.6%"
+    pub sync: Option<String>,
+}
+
+impl RaidStatus {
+    /// Returns whether at least one member of the array is missing or failed.
+    pub fn is_degraded(&self) -> bool {
+        self.health.contains('_')
+    }
+}
+
+/// A block device with the devices built on top of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockDevice {
+    /// Kernel name, e.g. "sda1" or "dm-0"
+    pub name: String,
+    /// Device-mapper name, e.g. "vg0-root"
+    pub dm_name: Option<String>,
+    pub kind: DeviceKind,
+    /// Size in bytes
+    pub size: u64,
+    /// Model of physical disks
+    pub model: Option<String>,
+    /// Mount point and filesystem type of the device
+    pub mount: Option<(String, String)>,
+    pub raid: Option<RaidStatus>,
+    /// Partitions and holders (dm devices, RAID arrays) of the device
+    pub children: Vec<BlockDevice>,
+}
+
+impl BlockDevice {
+    /// Returns the name shown in the tree, the device-mapper name if available.
+    pub fn get_display_name(&self) -> &str {
+        self.dm_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Parses the content of /proc/mdstat.
+///
+/// An array starts with a line like "md0 : active raid1 sdb1[1] sda1[0]", followed
+/// by lines with the member state "... [2/2] [UU]" and optionally a progress line
+/// like "[==>......]  recovery = 12.6% (...)".
+///
+/// See https://raid.wiki.kernel.org/index.php/Mdstat
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_mdstat(content: &str) -> HashMap<String, RaidStatus> {
+    let mut arrays = HashMap::new();
+    let mut current: Option<(String, RaidStatus)> = None;
+
+    for line in content.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+
+        // start of a new array, other lines like "Personalities : ..." are skipped
+        if !line.starts_with(char::is_whitespace) && columns.get(1) == Some(&":") {
+            if let Some((name, status)) = current.take() {
+                arrays.insert(name, status);
+            }
+            if !columns[0].starts_with("md") {
+                continue;
+            }
+            let active = columns.get(2) == Some(&"active");
+            let level = columns
+                .iter()
+                .skip(3)
+                .find(|c| c.starts_with("raid") || **c == "linear")
+                .map(|c| c.to_string())
+                .unwrap_or_default();
+            current = Some((
+                columns[0].to_string(),
+                RaidStatus {
+                    level,
+                    active,
+                    ..Default::default()
+                },
+            ));
+            continue;
+        }
+
+        let status = match &mut current {
+            Some((_, status)) => status,
+            None => continue,
+        };
+        if let Some(i) = columns
+            .iter()
+            .position(|c| c.starts_with('[') && c.contains('/'))
+        {
+            status.members = columns[i].to_string();
+            status.health = columns
+                .get(i + 1)
+                .map(|c| c.to_string())
+                .unwrap_or_default();
+        } else if let Some(i) = columns.iter().position(|c| *c == "=") {
+            let operation = i.checked_sub(1).and_then(|j| columns.get(j));
+            if let (Some(operation), Some(progress)) = (operation, columns.get(i + 1)) {
+                status.sync = Some(format!("{} {}", operation, progress));
+            }
+        }
+    }
+
+    if let Some((name, status)) = current {
+        arrays.insert(name, status);
+    }
+    arrays
+}
+
+/// Reads the first line of a sysfs attribute without the trailing newline.
+fn read_attribute(path: &Path) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?;
+    let value = value.lines().next()?.trim();
+    match value.is_empty() {
+        true => None,
+        false => Some(value.to_string()),
+    }
+}
+
+/// Returns the sorted names of the entries of a sysfs directory, empty if it doesn't exist.
+fn list_dir(path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = match read_dir(path) {
+        Ok(x) => x
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(_) => vec![],
+    };
+    names.sort();
+    names
+}
+
+/// Reads the block device tree from sysfs.
+///
+/// The roots are the devices in 'block' which aren't built on other devices, like physical
+/// disks. Their children are the partitions and the holders, e.g. device-mapper volumes or
+/// RAID arrays. Like "lsblk", a device with multiple slaves is shown below each of them.
+/// Unused loop devices are skipped.
+///
+/// # Arguments
+///
+/// * 'sys_root' - the sysfs mount point, usually "/sys"
+/// * 'raid' - state of the RAID arrays by name, see [parse_mdstat]
+/// * 'mounts' - the mounted filesystems
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_block_tree(
+    sys_root: &Path,
+    raid: &HashMap<String, RaidStatus>,
+    mounts: &[MountInfo],
+) -> Vec<BlockDevice> {
+    let class = sys_root.join("class").join("block");
+    let block = sys_root.join("block");
+
+    list_dir(&block)
+        .into_iter()
+        .filter(|name| list_dir(&class.join(name).join("slaves")).is_empty())
+        .filter_map(|name| read_device(&block, &class, &name, None, raid, mounts, 0))
+        .filter(|d| d.kind != DeviceKind::Loop || d.size > 0)
+        .collect()
+}
+
+/// Reads a block device and its children.
+///
+/// # Arguments
+///
+/// * 'block' - the 'block' directory of sysfs, containing the whole devices
+/// * 'class' - the 'class/block' directory of sysfs, containing all devices
+/// * 'name' - kernel name of the device
+/// * 'parent' - name of the whole device if the device is a partition
+/// * 'raid' - state of the RAID arrays by name
+/// * 'mounts' - the mounted filesystems
+/// * 'depth' - depth of the device in the tree
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_device(
+    block: &Path,
+    class: &Path,
+    name: &str,
+    parent: Option<&str>,
+    raid: &HashMap<String, RaidStatus>,
+    mounts: &[MountInfo],
+    depth: usize,
+) -> Option<BlockDevice> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    // partitions are located below their disk in 'block', holders only in 'class'
+    let path: PathBuf = match parent {
+        Some(parent) => block.join(parent).join(name),
+        None => class.join(name),
+    };
+    if !path.exists() {
+        return None;
+    }
+
+    let dm_name = read_attribute(&path.join("dm").join("name"));
+    let kind = if parent.is_some() || path.join("partition").exists() {
+        DeviceKind::Partition
+    } else if dm_name.is_some() {
+        match read_attribute(&path.join("dm").join("uuid")) {
+            Some(uuid) if uuid.starts_with("LVM-") => DeviceKind::Lvm,
+            _ => DeviceKind::DeviceMapper,
+        }
+    } else if name.starts_with("md") {
+        DeviceKind::Raid
+    } else if name.starts_with("loop") {
+        DeviceKind::Loop
+    } else {
+        DeviceKind::Disk
+    };
+
+    // the device number is the most reliable way to find the mount, but btrfs and
+    // others report a virtual device number, so the device path is checked too
+    let dev = read_attribute(&path.join("dev")).unwrap_or_default();
+    let mount = mounts
+        .iter()
+        .find(|m| {
+            m.device_id == dev
+                || m.source == format!("/dev/{}", name)
+                || dm_name
+                    .as_ref()
+                    .is_some_and(|n| m.source == format!("/dev/mapper/{}", n))
+        })
+        .map(|m| (m.mount_point.clone(), m.fs_type.clone()));
+
+    let mut children: Vec<BlockDevice> = Vec::new();
+    if parent.is_none() {
+        for entry in list_dir(&path) {
+            if path.join(&entry).join("partition").exists() {
+                children.extend(read_device(
+                    block,
+                    class,
+                    &entry,
+                    Some(name),
+                    raid,
+                    mounts,
+                    depth + 1,
+                ));
+            }
+        }
+    }
+    for holder in list_dir(&path.join("holders")) {
+        children.extend(read_device(
+            block,
+            class,
+            &holder,
+            None,
+            raid,
+            mounts,
+            depth + 1,
+        ));
+    }
+
+    Some(BlockDevice {
+        name: name.to_string(),
+        size: read_attribute(&path.join("size"))
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or_default()
+            * SECTOR_SIZE,
+        model: read_attribute(&path.join("device").join("model")),
+        dm_name,
+        kind,
+        mount,
+        raid: raid.get(name).cloned(),
+        children,
+    })
+}
+
+/// Initializes a thread to collect and send the block device tree every two seconds.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_data_collection_thread() -> mpsc::Receiver<Vec<BlockDevice>> {
+    let (tx, rx) = mpsc::channel();
+    let dur = time::Duration::from_millis(2000);
+
+    // Thread for the data collection
+    thread::spawn(move || loop {
+        let raid = parse_mdstat(&fs::read_to_string(PROC_MDSTAT).unwrap_or_default());
+        let mounts = match File::open(PROC_MOUNTINFO) {
+            Ok(file) => disk::parse_mountinfo(BufReader::new(file)),
+            Err(_) => vec![],
+        };
+
+        let _ = tx.send(read_block_tree(Path::new(SYS), &raid, &mounts));
+
+        thread::sleep(dur);
+    });
+
+    rx
+}
+
+/// A row of the tree table
+struct TreeRow<'a> {
+    /// Tree lines in front of the name
+    prefix: String,
+    device: &'a BlockDevice,
+}
+
+/// Flattens the device tree into rows with tree lines like "lsblk".
+///
+/// # Arguments
+///
+/// * 'devices' - the children of a device
+/// * 'indent' - tree lines of the parent levels
+/// * 'rows' - rows the devices are appended to
+fn flatten_children<'a>(devices: &'a [BlockDevice], indent: &str, rows: &mut Vec<TreeRow<'a>>) {
+    for (i, device) in devices.iter().enumerate() {
+        let last = i + 1 == devices.len();
+        rows.push(TreeRow {
+            prefix: format!("{}{}", indent, if last { "└─" } else { "├─" }),
+            device,
+        });
+        let indent = format!("{}{}", indent, if last { "  " } else { "│ " });
+        flatten_children(&device.children, &indent, rows);
+    }
+}
+
+/// Flattens the whole tree, the roots are shown without tree lines.
+fn flatten_tree(devices: &[BlockDevice]) -> Vec<TreeRow<'_>> {
+    let mut rows = Vec::new();
+    for device in devices {
+        rows.push(TreeRow {
+            prefix: String::new(),
+            device,
+        });
+        flatten_children(&device.children, "", &mut rows);
+    }
+    rows
+}
+
+/// Returns the status text of a device and its color.
+///
+/// RAID arrays show their level, members and a running sync, degraded arrays are red.
+fn get_status(device: &BlockDevice) -> (String, Color) {
+    match &device.raid {
+        Some(raid) => {
+            let mut text = format!("{} {} {}", raid.level, raid.members, raid.health);
+            if !raid.active {
+                text = format!("inactive {}", text);
+            }
+            if let Some(sync) = &raid.sync {
+                text = format!("{} {}", text, sync);
+            }
+            let color = if raid.is_degraded() || !raid.active {
+                Color::Red
+            } else if raid.sync.is_some() {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            (text, color)
+        }
+        None => (device.model.clone().unwrap_or_default(), Color::Reset),
+    }
+}
+
+/// Stores the state data of the widget.
+pub struct BlockTreeWidget {
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected row.
+    item_index: usize,
+    /// The root devices of the tree.
+    devices: Vec<BlockDevice>,
+    /// Used to receive data from the thread which reads the block devices.
+    dc_thread: mpsc::Receiver<Vec<BlockDevice>>,
+}
+
+impl BlockTreeWidget {
+    /// Creates new block device widget with default values.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new() -> Self {
+        let mut a = Self {
+            table_state: TableState::default(),
+            item_index: 0,
+            devices: vec![],
+            dc_thread: init_data_collection_thread(),
+        };
+        a.table_state.select(Some(0));
+        a
+    }
+
+    /// Retrieves information from the update thread and store the new data in the widget.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        // Recv data from the data collector thread
+        if let Ok(devices) = self.dc_thread.try_recv() {
+            self.devices = devices;
+
+            // Keep the selection inside the table
+            let len = flatten_tree(&self.devices).len();
+            if self.item_index >= len {
+                self.item_index = len.saturating_sub(1);
+                self.table_state.select(Some(self.item_index));
+            }
+        }
+    }
+
+    /// Draws the block devices as a tree.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header = Row::new(vec![
+            "Name",
+            "Type",
+            "Size",
+            "Mount",
+            "FS",
+            "Model / Status",
+        ])
+        .style(header_style)
+        .height(1);
+
+        // Populate rows of table
+        let tree = flatten_tree(&self.devices);
+        let rows = tree.iter().map(|r| {
+            let (mount_point, fs_type) = r.device.mount.clone().unwrap_or_default();
+            let (status, color) = get_status(r.device);
+            Row::new(vec![
+                Cell::from(format!("{}{}", r.prefix, r.device.get_display_name())),
+                Cell::from(r.device.kind.get_name()),
+                Cell::from(format!(
+                    "{: >9}",
+                    util::to_humanreadable(r.device.size as usize)
+                )),
+                Cell::from(mount_point),
+                Cell::from(fs_type),
+                Cell::from(status).style(Style::default().fg(color)),
+            ])
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(block)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(24),
+                Constraint::Length(5),
+                Constraint::Length(9),
+                Constraint::Length(20),
+                Constraint::Length(8),
+                Constraint::Min(10),
+            ]);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        match key {
+            // Navigate by row
+            Key::Down if self.item_index + 1 < flatten_tree(&self.devices).len() => {
+                self.item_index += 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            Key::Up if self.item_index > 0 => {
+                self.item_index -= 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::os::unix::fs::symlink;
+
+    const MDSTAT: &str = "Personalities : [raid1] [raid6] [raid5] [raid4]
+md0 : active raid1 sdb1[1] sda2[0]
+      10476544 blocks super 1.2 [2/1] [U_]
+      [==>..................]  recovery = 12.6% (1320192/10476544) finish=1.6min speed=94299K/sec
+
+md1 : active raid5 sdd[2] sdc[1] sde[0]
+      20953088 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/3] [UUU]
+
+md2 : inactive sdf[0](S)
+      1048576 blocks
+
+unused devices: <none>
+";
+
+    /// A fake sysfs in a temporary directory, removed when dropped.
+    ///
+    /// Like the real sysfs, the entries of 'block' and 'class/block' are symlinks to the
+    /// device directories and partitions are subdirectories of their disk.
+    struct FakeSysfs {
+        root: PathBuf,
+    }
+
+    impl FakeSysfs {
+        fn new(name: &str) -> Self {
+            let root = env::temp_dir().join(format!("nmtop-sysfs-{}-{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("block")).unwrap();
+            fs::create_dir_all(root.join("class").join("block")).unwrap();
+            FakeSysfs { root }
+        }
+
+        /// Writes an attribute of a device, e.g. "dm/name".
+        fn write(&self, device: &str, attribute: &str, content: &str) {
+            let path = self
+                .root
+                .join("class")
+                .join("block")
+                .join(device)
+                .join(attribute);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, format!("{}\n", content)).unwrap();
+        }
+
+        /// Creates a device directory with its links to holders and slaves.
+        fn create(&self, path: &Path, holders: &[&str], slaves: &[&str]) {
+            fs::create_dir_all(path.join("holders")).unwrap();
+            fs::create_dir_all(path.join("slaves")).unwrap();
+            for h in holders {
+                symlink(
+                    self.root.join("class").join("block").join(h),
+                    path.join("holders").join(h),
+                )
+                .unwrap();
+            }
+            for s in slaves {
+                symlink(
+                    self.root.join("class").join("block").join(s),
+                    path.join("slaves").join(s),
+                )
+                .unwrap();
+            }
+        }
+
+        /// Adds a whole device with the given size in sectors, holders and slaves.
+        fn add_device(
+            &self,
+            name: &str,
+            dev: &str,
+            sectors: u64,
+            holders: &[&str],
+            slaves: &[&str],
+        ) {
+            let path = self.root.join("devices").join(name);
+            self.create(&path, holders, slaves);
+            symlink(&path, self.root.join("block").join(name)).unwrap();
+            symlink(&path, self.root.join("class").join("block").join(name)).unwrap();
+            self.write(name, "dev", dev);
+            self.write(name, "size", &sectors.to_string());
+        }
+
+        /// Adds a partition below its disk.
+        fn add_partition(&self, disk: &str, name: &str, dev: &str, sectors: u64, holders: &[&str]) {
+            let path = self.root.join("devices").join(disk).join(name);
+            self.create(&path, holders, &[]);
+            symlink(&path, self.root.join("class").join("block").join(name)).unwrap();
+            self.write(name, "dev", dev);
+            self.write(name, "size", &sectors.to_string());
+            self.write(name, "partition", "1");
+        }
+    }
+
+    impl Drop for FakeSysfs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// sda (sda1, sda2) and sdb (sdb1), md0 as RAID1 of sda2 and sdb1, an LVM volume on md0
+    /// and an unused loop device.
+    fn create_fixture(name: &str) -> FakeSysfs {
+        let sys = FakeSysfs::new(name);
+        sys.add_device("sda", "8:0", 2000, &[], &[]);
+        sys.write("sda", "device/model", "FAKE DISK");
+        sys.add_partition("sda", "sda1", "8:1", 500, &[]);
+        sys.add_partition("sda", "sda2", "8:2", 1500, &["md0"]);
+        sys.add_device("sdb", "8:16", 1500, &[], &[]);
+        sys.add_partition("sdb", "sdb1", "8:17", 1500, &["md0"]);
+        sys.add_device("md0", "9:0", 1400, &["dm-0"], &["sda2", "sdb1"]);
+        sys.add_device("dm-0", "253:0", 1000, &[], &["md0"]);
+        sys.write("dm-0", "dm/name", "vg0-root");
+        sys.write("dm-0", "dm/uuid", "LVM-abcdef");
+        sys.add_device("loop0", "7:0", 0, &[], &[]);
+        sys
+    }
+
+    fn mount(device_id: &str, source: &str, mount_point: &str, fs_type: &str) -> MountInfo {
+        MountInfo {
+            device_id: device_id.to_string(),
+            source: source.to_string(),
+            mount_point: mount_point.to_string(),
+            fs_type: fs_type.to_string(),
+            options: "rw".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_mdstat_arrays() {
+        let arrays = parse_mdstat(MDSTAT);
+        assert_eq!(arrays.len(), 3);
+
+        let md0 = &arrays["md0"];
+        assert_eq!(md0.level, "raid1");
+        assert!(md0.active);
+        assert_eq!(md0.members, "[2/1]");
+        assert_eq!(md0.health, "[U_]");
+        assert_eq!(md0.sync.as_deref(), Some("recovery 12.6%"));
+        assert!(md0.is_degraded());
+
+        let md1 = &arrays["md1"];
+        assert_eq!(md1.level, "raid5");
+        assert_eq!(md1.health, "[UUU]");
+        assert_eq!(md1.sync, None);
+        assert!(!md1.is_degraded());
+
+        assert!(!arrays["md2"].active);
+        assert!(parse_mdstat("").is_empty());
+
+        // A progress line starting with "=" has no operation
+        let arrays = parse_mdstat("md3 : active raid1 sda1[0]\n      = 5.0%\n");
+        assert_eq!(arrays["md3"].sync, None);
+    }
+
+    #[test]
+    fn tree_structure() {
+        let sys = create_fixture("tree");
+        let tree = read_block_tree(&sys.root, &HashMap::new(), &[]);
+
+        // md0 and dm-0 have slaves, loop0 is unused
+        let roots: Vec<&str> = tree.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(roots, vec!["sda", "sdb"]);
+
+        let sda = &tree[0];
+        assert_eq!(sda.kind, DeviceKind::Disk);
+        assert_eq!(sda.size, 2000 * SECTOR_SIZE);
+        assert_eq!(sda.model.as_deref(), Some("FAKE DISK"));
+        let parts: Vec<&str> = sda.children.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(parts, vec!["sda1", "sda2"]);
+        assert_eq!(sda.children[0].kind, DeviceKind::Partition);
+
+        // the array is shown below both members
+        let md0 = &sda.children[1].children[0];
+        assert_eq!(md0.name, "md0");
+        assert_eq!(md0.kind, DeviceKind::Raid);
+        assert_eq!(tree[1].children[0].children[0], *md0);
+
+        let lv = &md0.children[0];
+        assert_eq!(lv.kind, DeviceKind::Lvm);
+        assert_eq!(lv.get_display_name(), "vg0-root");
+
+        let rows: Vec<String> = flatten_tree(&tree)
+            .iter()
+            .map(|r| format!("{}{}", r.prefix, r.device.get_display_name()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "sda",
+                "├─sda1",
+                "└─sda2",
+                "  └─md0",
+                "    └─vg0-root",
+                "sdb",
+                "└─sdb1",
+                "  └─md0",
+                "    └─vg0-root",
+            ]
+        );
+    }
+
+    #[test]
+    fn raid_status() {
+        let sys = create_fixture("raid");
+        let tree = read_block_tree(&sys.root, &parse_mdstat(MDSTAT), &[]);
+
+        let md0 = &tree[0].children[1].children[0];
+        let (status, color) = get_status(md0);
+        assert_eq!(status, "raid1 [2/1] [U_] recovery 12.6%");
+        assert_eq!(color, Color::Red);
+        assert!(tree[0].children[0].raid.is_none());
+    }
+
+    #[test]
+    fn mounted_filesystems() {
+        let sys = create_fixture("mounts");
+        let mounts = vec![
+            mount("8:1", "/dev/sda1", "/boot", "ext4"),
+            // btrfs reports a virtual device number
+            mount("0:42", "/dev/mapper/vg0-root", "/", "btrfs"),
+        ];
+        let tree = read_block_tree(&sys.root, &HashMap::new(), &mounts);
+
+        let sda1 = &tree[0].children[0];
+        assert_eq!(sda1.mount, Some(("/boot".to_string(), "ext4".to_string())));
+        let lv = &tree[0].children[1].children[0].children[0];
+        assert_eq!(lv.mount, Some(("/".to_string(), "btrfs".to_string())));
+        assert_eq!(tree[1].mount, None);
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Stores user settings which are remembered between two runs of nmtop.
+///
+/// The settings are stored as simple "key = value" lines in
+/// $XDG_CONFIG_HOME/nmtop/config (or ~/.config/nmtop/config).
+#[derive(Default, Debug)]
+pub struct Config {
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Returns the path of the config file or None if no home directory is known.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn get_path() -> Option<PathBuf> {
+        let base = match env::var_os("XDG_CONFIG_HOME") {
+            Some(x) if !x.is_empty() => PathBuf::from(x),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(base.join("nmtop").join("config"))
+    }
+
+    /// Loads the config file.
+    ///
+    /// Returns an empty config if the file does not exist or could not be read, so the
+    /// widgets fall back to their defaults. Malformed lines are ignored.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn load() -> Self {
+        let mut config: Self = Default::default();
+
+        let content = match Self::get_path().map(fs::read_to_string) {
+            Some(Ok(x)) => x,
+            _ => return config,
+        };
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                config
+                    .values
+                    .insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        config
+    }
+
+    /// Writes the config file, creating the config directory if necessary.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::get_path().ok_or("no home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = File::create(path)?;
+        for (key, value) in &self.values {
+            writeln!(file, "{} = {}", key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a setting.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|v| v.as_str())
+    }
+
+    /// Updates the value of a setting.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+}
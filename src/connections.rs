@@ -0,0 +1,1029 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fs::{self, read_dir};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
+use std::{mem, ptr};
+use std::{thread, time};
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    terminal::Frame,
+    text::Spans,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+
+/// Names of the columns by column id
+const COLUMN_NAMES: [&str; 8] = [
+    "Proto", "Local", "Remote", "State", "Recv-Q", "Send-Q", "PID", "Process",
+];
+
+/// Widths of the columns by column id
+const COLUMN_WIDTHS: [u16; 8] = [6, 45, 45, 12, 8, 8, 8, 20];
+
+/// Id of the PID column
+const PID_COLUMN: usize = 6;
+
+/// Protocol of a socket, defines the file in /proc/net it was read from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    /// Returns the name of the protocol, which is also the file name in /proc/net.
+    pub fn get_name(&self) -> &str {
+        match *self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+            Protocol::Unix => "unix",
+        }
+    }
+
+    fn is_tcp(&self) -> bool {
+        matches!(self, Protocol::Tcp | Protocol::Tcp6)
+    }
+}
+
+/// A socket with the process it belongs to
+#[derive(Debug, Clone)]
+pub struct Socket {
+    pub protocol: Protocol,
+    /// Local address, the path for unix sockets
+    pub local_address: String,
+    /// None for unix sockets
+    pub local_port: Option<u16>,
+    /// Remote address, empty for unix sockets
+    pub remote_address: String,
+    /// None for unix sockets
+    pub remote_port: Option<u16>,
+    /// State like "ESTABLISHED" or "LISTEN"
+    pub state: String,
+    /// Bytes in the receive queue
+    pub rx_queue: u64,
+    /// Bytes in the send queue
+    pub tx_queue: u64,
+    /// Owner of the socket, 0 for unix sockets
+    pub uid: u32,
+    /// Name of the owner, empty for unix sockets
+    pub user: String,
+    /// Inode of the socket, used to find the owning process
+    pub inode: u64,
+    /// Pids and names of the processes which have the socket open, sorted by pid.
+    /// Empty if unknown (e.g. missing permissions)
+    pub owners: Vec<(usize, String)>,
+}
+
+impl Socket {
+    /// Returns the local address with the port, like "127.0.0.1:22" or "[::1]:22".
+    pub fn get_local(&self) -> String {
+        format_address(&self.local_address, self.local_port)
+    }
+
+    /// Returns the remote address with the port.
+    pub fn get_remote(&self) -> String {
+        format_address(&self.remote_address, self.remote_port)
+    }
+
+    /// Returns the pid of the first process which has the socket open.
+    pub fn get_pid(&self) -> Option<usize> {
+        self.owners.first().map(|(pid, _)| *pid)
+    }
+
+    /// Returns the name of the first process with the number of further processes,
+    /// like "nginx +4" if the socket is shared by a forking server.
+    pub fn get_process(&self) -> String {
+        match self.owners.as_slice() {
+            [] => String::new(),
+            [(_, name)] => name.clone(),
+            [(_, name), others @ ..] => format!("{} +{}", name, others.len()),
+        }
+    }
+
+    /// Checks whether the socket is a listening TCP or a bound UDP socket.
+    pub fn is_listening(&self) -> bool {
+        match self.protocol {
+            Protocol::Unix => false,
+            p if p.is_tcp() => self.state == "LISTEN",
+            _ => self.state == "UNCONN" && self.local_port.unwrap_or_default() != 0,
+        }
+    }
+
+    /// Checks whether the socket is bound to all interfaces (0.0.0.0 or ::).
+    pub fn is_bound_to_all(&self) -> bool {
+        self.local_address
+            .parse::<IpAddr>()
+            .is_ok_and(|x| x.is_unspecified())
+    }
+}
+
+/// Joins an address and a port, IPv6 addresses are put in brackets.
+fn format_address(address: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) if address.contains(':') => format!("[{}]:{}", address, port),
+        Some(port) => format!("{}:{}", address, port),
+        None => address.to_string(),
+    }
+}
+
+/// Parses an address from /proc/net/tcp and similar files, e.g. "0100007F:0016".
+///
+/// The address is printed as 32 bit words in host byte order, the port in hex.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn parse_address(value: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::new();
+    for i in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let address = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&bytes);
+            let address = Ipv6Addr::from(octets);
+            // show IPv4 mapped addresses like "::ffff:127.0.0.1" as IPv4
+            match address.to_ipv4_mapped() {
+                Some(x) => IpAddr::V4(x),
+                None => IpAddr::V6(address),
+            }
+        }
+        _ => return None,
+    };
+    Some((address, port))
+}
+
+/// Returns the name of a TCP state from the kernel.
+fn get_tcp_state(state: u8) -> &'static str {
+    match state {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parses the content of /proc/net/tcp, tcp6, udp or udp6.
+///
+/// # Arguments
+///
+/// * 'content' - the content of the file, including the header line
+/// * 'protocol' - the protocol of the file
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_inet_sockets(content: &str, protocol: Protocol) -> Vec<Socket> {
+    let mut sockets = Vec::new();
+
+    for line in content.lines().skip(1) {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 10 {
+            continue;
+        }
+        let (local, remote) = match (parse_address(columns[1]), parse_address(columns[2])) {
+            (Some(l), Some(r)) => (l, r),
+            _ => continue,
+        };
+        let state = u8::from_str_radix(columns[3], 16).unwrap_or_default();
+        let state = match protocol.is_tcp() {
+            true => get_tcp_state(state),
+            // UDP sockets only use the established and close state
+            false if state == 0x01 => "ESTABLISHED",
+            false => "UNCONN",
+        };
+        let (tx_queue, rx_queue) = columns[4].split_once(':').unwrap_or_default();
+
+        sockets.push(Socket {
+            protocol,
+            local_address: local.0.to_string(),
+            local_port: Some(local.1),
+            remote_address: remote.0.to_string(),
+            remote_port: Some(remote.1),
+            state: state.to_string(),
+            rx_queue: u64::from_str_radix(rx_queue, 16).unwrap_or_default(),
+            tx_queue: u64::from_str_radix(tx_queue, 16).unwrap_or_default(),
+            uid: columns[7].parse().unwrap_or_default(),
+            user: String::new(),
+            inode: columns[9].parse().unwrap_or_default(),
+            owners: Vec::new(),
+        });
+    }
+
+    sockets
+}
+
+/// Parses the content of /proc/net/unix.
+///
+/// Each line contains the kernel address, reference count, protocol, flags, type,
+/// state, inode and optionally the path of the socket.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_unix_sockets(content: &str) -> Vec<Socket> {
+    let mut sockets = Vec::new();
+
+    for line in content.lines().skip(1) {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 7 {
+            continue;
+        }
+        // sockets accepting connections have the __SO_ACCEPTCON flag
+        let flags = u32::from_str_radix(columns[3], 16).unwrap_or_default();
+        let state = match u8::from_str_radix(columns[5], 16).unwrap_or_default() {
+            _ if flags & 0x10000 != 0 => "LISTEN",
+            0x01 => "UNCONN",
+            0x02 => "CONNECTING",
+            0x03 => "CONNECTED",
+            0x04 => "DISCONNECTING",
+            _ => "UNKNOWN",
+        };
+
+        sockets.push(Socket {
+            protocol: Protocol::Unix,
+            // paths may contain spaces
+            local_address: columns.get(7..).unwrap_or_default().join(" "),
+            local_port: None,
+            remote_address: String::new(),
+            remote_port: None,
+            state: state.to_string(),
+            rx_queue: 0,
+            tx_queue: 0,
+            uid: 0,
+            user: String::new(),
+            inode: columns[6].parse().unwrap_or_default(),
+            owners: Vec::new(),
+        });
+    }
+
+    sockets
+}
+
+/// Maps socket inodes to the pids and names of the processes which have them open.
+///
+/// The links in /proc/<pid>/fd point to "socket:[<inode>]" for sockets. Only the
+/// processes of the current user can be read without root. Sockets can be shared,
+/// e.g. by a forking server, the owners are sorted by pid.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_socket_owners() -> HashMap<u64, Vec<(usize, String)>> {
+    let mut owners: HashMap<u64, Vec<(usize, String)>> = HashMap::new();
+
+    let mut pids: Vec<usize> = match read_dir("/proc") {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().to_string_lossy().parse().ok())
+            .collect(),
+        Err(_) => return owners,
+    };
+    pids.sort_unstable();
+
+    for pid in pids {
+        let fds = match read_dir(format!("/proc/{}/fd", pid)) {
+            Ok(x) => x,
+            Err(_) => continue,
+        };
+        let mut name = None;
+        for fd in fds.filter_map(|e| e.ok()) {
+            let target = match fs::read_link(fd.path()) {
+                Ok(x) => x.to_string_lossy().to_string(),
+                Err(_) => continue,
+            };
+            let inode = match target
+                .strip_prefix("socket:[")
+                .and_then(|x| x.strip_suffix(']'))
+                .and_then(|x| x.parse::<u64>().ok())
+            {
+                Some(x) => x,
+                None => continue,
+            };
+            let name = name.get_or_insert_with(|| {
+                fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .map(|x| x.trim().to_string())
+                    .unwrap_or_default()
+            });
+            let entry = owners.entry(inode).or_default();
+            // a process can have the same socket open multiple times, e.g. after dup
+            if entry.last().map(|(p, _)| *p) != Some(pid) {
+                entry.push((pid, name.clone()));
+            }
+        }
+    }
+
+    owners
+}
+
+/// Looks up the name of a user like "ls" does, also for users of NSS sources like LDAP.
+///
+/// Returns None if the user doesn't exist.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn get_user_name(uid: u32) -> Option<String> {
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        // SAFETY: getpwuid_r only writes into the provided struct and buffer,
+        // the strings of the entry point into the buffer
+        let mut entry: libc::passwd = unsafe { mem::zeroed() };
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let error = unsafe {
+            libc::getpwuid_r(
+                uid,
+                &mut entry,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+        match error {
+            // the entry doesn't fit, retry with a larger buffer
+            libc::ERANGE if buffer.len() < 1 << 20 => buffer.resize(buffer.len() * 2, 0),
+            0 if !result.is_null() => {
+                let name = unsafe { CStr::from_ptr(entry.pw_name) };
+                return Some(name.to_string_lossy().into_owned());
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Reads all TCP, UDP and unix sockets, their owners and processes.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn get_sockets() -> Vec<Socket> {
+    let mut sockets = Vec::new();
+
+    for protocol in [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6] {
+        // the IPv6 files are missing if IPv6 is disabled
+        if let Ok(content) = fs::read_to_string(format!("/proc/net/{}", protocol.get_name())) {
+            sockets.extend(parse_inet_sockets(&content, protocol));
+        }
+    }
+    if let Ok(content) = fs::read_to_string("/proc/net/unix") {
+        sockets.extend(parse_unix_sockets(&content));
+    }
+
+    let mut owners = read_socket_owners();
+    // most sockets belong to a few users
+    let mut users: HashMap<u32, String> = HashMap::new();
+    for socket in sockets.iter_mut() {
+        if socket.protocol != Protocol::Unix {
+            socket.user = users
+                .entry(socket.uid)
+                .or_insert_with(|| {
+                    get_user_name(socket.uid).unwrap_or_else(|| socket.uid.to_string())
+                })
+                .clone();
+        }
+        if let Some(x) = owners.remove(&socket.inode) {
+            socket.owners = x;
+        }
+    }
+
+    sockets
+}
+
+/// Initializes a thread to collect and send the sockets each 2 seconds.
+///
+/// Finding the owners requires reading the fds of all processes, so the sockets are
+/// only read while they are shown.
+///
+/// # Arguments
+///
+/// * 'active' - whether the sockets are shown, can be changed while the thread runs
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_data_collection_thread(active: Arc<AtomicBool>) -> mpsc::Receiver<Vec<Socket>> {
+    let (tx, rx) = mpsc::channel();
+
+    let dur = time::Duration::from_millis(2000);
+    let idle_dur = time::Duration::from_millis(100);
+
+    // Thread for the data collection
+    thread::spawn(move || loop {
+        if active.load(AtomicOrdering::Relaxed) {
+            let _ = tx.send(get_sockets());
+            thread::sleep(dur);
+        } else {
+            // check often, so the sockets appear quickly once the view is shown
+            thread::sleep(idle_dur);
+        }
+    });
+
+    rx
+}
+
+/// Stores the state data of the widget.
+pub struct ConnectionsWidget {
+    /// All sockets from the last update
+    sockets: Vec<Socket>,
+    /// The shown sockets after filtering and sorting
+    shown: Vec<Socket>,
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected item.
+    item_index: usize,
+    /// Index of column to sort by.
+    sort_index: usize,
+    /// Index of current selected column.
+    column_index: usize,
+    /// Sort the column descending or ascending.
+    sort_descending: bool,
+    /// Index of column to filter by.
+    filter_index: Option<usize>,
+    /// The filter value
+    filter_value: String,
+    /// Whether unix sockets are shown
+    show_unix: bool,
+    /// Store whether the filter popup is open or closed
+    popup_open: bool,
+    /// Temporary field to store popup input.
+    input: String,
+    /// Listening TCP and bound UDP sockets, sorted by port
+    listening: Vec<Socket>,
+    /// Used for the selection of the current row of the listening ports.
+    listening_state: TableState,
+    /// Index of the current selected listening port.
+    listening_index: usize,
+    /// Tells the data collection thread whether the sockets are shown.
+    active: Arc<AtomicBool>,
+    /// Used to receive data from the thread which reads the sockets.
+    dc_thread: mpsc::Receiver<Vec<Socket>>,
+}
+
+impl ConnectionsWidget {
+    /// Creates a new connections widget with default values.
+    pub fn new() -> Self {
+        let active = Arc::new(AtomicBool::new(false));
+        let mut a = Self {
+            sockets: Vec::new(),
+            shown: Vec::new(),
+            table_state: TableState::default(),
+            item_index: 0,
+            sort_index: 0,
+            column_index: 0,
+            sort_descending: false,
+            filter_index: None,
+            filter_value: String::new(),
+            show_unix: true,
+            popup_open: false,
+            input: String::new(),
+            listening: Vec::new(),
+            listening_state: TableState::default(),
+            listening_index: 0,
+            active: active.clone(),
+            dc_thread: init_data_collection_thread(active),
+        };
+        a.table_state.select(Some(0));
+        a.listening_state.select(Some(0));
+        a
+    }
+
+    /// Retrieves information from the update thread and store the new data in the widget.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        if let Some(sockets) = self.dc_thread.try_iter().last() {
+            self.sockets = sockets;
+            self.refresh();
+            self.refresh_listening();
+        }
+    }
+
+    /// Starts or stops reading the sockets, they only need to be read while the
+    /// connections or the listening ports are shown.
+    ///
+    /// # Arguments
+    ///
+    /// * 'active' - whether one of the views is shown
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, AtomicOrdering::Relaxed);
+    }
+
+    /// Collects the listening sockets, sockets of the same process bound to the same
+    /// address and port (SO_REUSEPORT) are shown once.
+    fn refresh_listening(&mut self) {
+        let mut listening: Vec<Socket> = self
+            .sockets
+            .iter()
+            .filter(|s| s.is_listening())
+            .cloned()
+            .collect();
+        listening.sort_by(|a, b| {
+            a.local_port
+                .cmp(&b.local_port)
+                .then(a.protocol.get_name().cmp(b.protocol.get_name()))
+                .then(a.local_address.cmp(&b.local_address))
+                // duplicates have to be adjacent for dedup_by
+                .then(a.owners.cmp(&b.owners))
+        });
+        listening.dedup_by(|a, b| {
+            a.protocol == b.protocol
+                && a.local_address == b.local_address
+                && a.local_port == b.local_port
+                && a.owners == b.owners
+        });
+
+        self.listening = listening;
+        self.listening_index = self
+            .listening_index
+            .min(self.listening.len().saturating_sub(1));
+        self.listening_state.select(Some(self.listening_index));
+    }
+
+    /// Returns the number of listening sockets bound to all interfaces.
+    pub fn get_exposed_count(&self) -> usize {
+        self.listening
+            .iter()
+            .filter(|s| s.is_bound_to_all())
+            .count()
+    }
+
+    /// Shows only the sockets which the given process has open.
+    ///
+    /// # Arguments
+    ///
+    /// * 'pid' - the process id, e.g. of the process selected in the process table
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn show_process(&mut self, pid: usize) {
+        self.filter_index = Some(PID_COLUMN);
+        self.filter_value = pid.to_string();
+        self.item_index = 0;
+        self.refresh();
+    }
+
+    /// Returns a description of the active filter for the title, if any.
+    pub fn get_filter(&self) -> Option<String> {
+        self.filter_index
+            .map(|i| format!("{}: {}", COLUMN_NAMES[i], self.filter_value))
+    }
+
+    /// Checks whether a socket matches the current filter.
+    ///
+    /// Address columns match the port if the value is a number, otherwise a part of
+    /// the address.
+    fn filter(&self, s: &Socket) -> bool {
+        if !self.show_unix && s.protocol == Protocol::Unix {
+            return false;
+        }
+        let value = &self.filter_value;
+        let address = |address: String, port: Option<u16>| match value.parse::<u16>() {
+            Ok(v) => port == Some(v),
+            Err(_) => address.contains(value.as_str()),
+        };
+        match self.filter_index {
+            Some(0) => s.protocol.get_name().starts_with(value.as_str()),
+            Some(1) => address(s.get_local(), s.local_port),
+            Some(2) => address(s.get_remote(), s.remote_port),
+            Some(3) => s.state.contains(&value.to_uppercase()),
+            Some(6) => s.owners.iter().any(|(pid, _)| pid.to_string() == *value),
+            Some(7) => s
+                .owners
+                .iter()
+                .any(|(_, name)| name.contains(value.as_str())),
+            _ => true,
+        }
+    }
+
+    /// Filters and sorts the sockets by the current selected columns.
+    fn refresh(&mut self) {
+        let mut shown: Vec<Socket> = self
+            .sockets
+            .iter()
+            .filter(|s| self.filter(s))
+            .cloned()
+            .collect();
+
+        let sort_index = self.sort_index;
+        shown.sort_by(|a, b| {
+            let s = match sort_index {
+                0 => a.protocol.get_name().cmp(b.protocol.get_name()),
+                1 => a
+                    .local_address
+                    .cmp(&b.local_address)
+                    .then(a.local_port.cmp(&b.local_port)),
+                2 => a
+                    .remote_address
+                    .cmp(&b.remote_address)
+                    .then(a.remote_port.cmp(&b.remote_port)),
+                3 => a.state.cmp(&b.state),
+                4 => a.rx_queue.cmp(&b.rx_queue),
+                5 => a.tx_queue.cmp(&b.tx_queue),
+                6 => a.get_pid().cmp(&b.get_pid()),
+                7 => a.get_process().cmp(&b.get_process()),
+                _ => Ordering::Equal,
+            };
+            if self.sort_descending {
+                Ordering::reverse(s)
+            } else {
+                s
+            }
+        });
+
+        self.shown = shown;
+        self.item_index = self.item_index.min(self.shown.len().saturating_sub(1));
+        self.table_state.select(Some(self.item_index));
+    }
+
+    /// Returns the cell of a socket for the given column id.
+    fn get_cell(s: &Socket, column: usize) -> Cell<'static> {
+        match column {
+            0 => Cell::from(s.protocol.get_name().to_string()),
+            1 => Cell::from(s.get_local()),
+            2 => Cell::from(s.get_remote()),
+            3 => Cell::from(s.state.clone()).style(match s.state.as_str() {
+                "LISTEN" => Style::default().fg(Color::Green),
+                "ESTABLISHED" | "CONNECTED" => Style::default().fg(Color::Cyan),
+                _ => Style::default(),
+            }),
+            4 => Cell::from(format!("{: >7}", s.rx_queue)),
+            5 => Cell::from(format!("{: >7}", s.tx_queue)),
+            6 => Cell::from(match s.get_pid() {
+                Some(pid) => format!("{: >7}", pid),
+                None => format!("{: >7}", "-"),
+            }),
+            7 => Cell::from(s.get_process()),
+            _ => Cell::from(""),
+        }
+    }
+
+    /// Draws the sockets in a table.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header_cells = COLUMN_NAMES.iter().enumerate().map(|(i, name)| {
+            if i == self.column_index {
+                Cell::from(*name).style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+            } else {
+                Cell::from(*name)
+            }
+        });
+        let header = Row::new(header_cells).style(header_style).height(1);
+
+        // Populate rows of table
+        let rows = self.shown.iter().map(|s| {
+            let cells = (0..COLUMN_NAMES.len()).map(|i| Self::get_cell(s, i));
+            Row::new(cells)
+        });
+
+        let widths: Vec<Constraint> = COLUMN_WIDTHS
+            .iter()
+            .map(|w| Constraint::Length(*w))
+            .collect();
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(block)
+            .highlight_style(selected_style)
+            .widths(&widths);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+
+        if self.popup_open {
+            self.draw_popup(f, rect);
+        }
+    }
+
+    /// Draws the listening TCP and bound UDP ports.
+    ///
+    /// Ports bound to all interfaces are highlighted, since they are reachable from
+    /// the network unless a firewall blocks them.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw_listening<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header = Row::new(vec![
+            "Proto", "Port", "Address", "Scope", "PID", "Process", "User",
+        ])
+        .style(header_style)
+        .height(1);
+
+        // Populate rows of table
+        let rows = self.listening.iter().map(|s| {
+            let is_loopback = s
+                .local_address
+                .parse::<IpAddr>()
+                .is_ok_and(|x| x.is_loopback());
+            let (scope, style) = if s.is_bound_to_all() {
+                ("all interfaces", Style::default().fg(Color::Yellow))
+            } else if is_loopback {
+                ("loopback", Style::default().fg(Color::Green))
+            } else {
+                ("single address", Style::default())
+            };
+            Row::new(vec![
+                Cell::from(s.protocol.get_name().to_string()),
+                Cell::from(format!("{: >5}", s.local_port.unwrap_or_default())),
+                Cell::from(s.local_address.clone()),
+                Cell::from(scope),
+                Cell::from(match s.get_pid() {
+                    Some(pid) => format!("{: >7}", pid),
+                    None => format!("{: >7}", "-"),
+                }),
+                Cell::from(s.get_process()),
+                Cell::from(s.user.clone()),
+            ])
+            .style(style)
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(block)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(40),
+                Constraint::Length(15),
+                Constraint::Length(8),
+                Constraint::Length(20),
+                Constraint::Length(16),
+            ]);
+        f.render_stateful_widget(table, rect, &mut self.listening_state);
+    }
+
+    /// Draws the input popup for the filter value over the table.
+    fn draw_popup<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(50),
+                    Constraint::Percentage(25),
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+        let popup = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(rect.height.saturating_sub(8) / 2),
+                    Constraint::Length(8),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(horizontal[1]);
+
+        let hint = match self.column_index {
+            1 | 2 => "Port or part of the address",
+            _ => "",
+        };
+        let text = vec![
+            Spans::from(hint),
+            Spans::from(self.input.to_string()),
+            Spans::default(),
+            Spans::from("CTRL-x to cancel"),
+            Spans::from("Press Enter to apply"),
+        ];
+
+        let block = Block::default()
+            .style(Style::default().fg(Color::Yellow))
+            .title(format!("Filter by {}", COLUMN_NAMES[self.column_index]))
+            .borders(Borders::ALL);
+        let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+        f.render_widget(Clear, popup[1]);
+        f.render_widget(paragraph, popup[1]);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        if self.popup_open {
+            match key {
+                Key::Backspace => {
+                    self.input.pop();
+                }
+                // Filter by selected column
+                Key::Char('\n') => {
+                    self.filter_index = Some(self.column_index);
+                    self.filter_value = std::mem::take(&mut self.input);
+                    self.popup_open = false;
+                    self.item_index = 0;
+                    self.refresh();
+                }
+                // Cancel input
+                Key::Ctrl('x') => {
+                    self.input.clear();
+                    self.popup_open = false;
+                }
+                Key::Char(c) => self.input.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match key {
+            // Navigate by row
+            Key::Down if self.item_index + 1 < self.shown.len() => {
+                self.item_index += 1;
+            }
+            Key::Up if self.item_index > 0 => {
+                self.item_index -= 1;
+            }
+            // Navigate by column
+            Key::Right if self.column_index + 1 < COLUMN_NAMES.len() => {
+                self.column_index += 1;
+            }
+            Key::Left if self.column_index > 0 => {
+                self.column_index -= 1;
+            }
+            // Filter by selected column, the queues can't be filtered
+            Key::Char('f') if !matches!(self.column_index, 4 | 5) => {
+                self.popup_open = true;
+            }
+            // Reset filter
+            Key::Char('r') => {
+                self.filter_index = None;
+                self.refresh();
+            }
+            // Show/hide unix sockets
+            Key::Char('u') => {
+                self.show_unix = !self.show_unix;
+                self.refresh();
+            }
+            // Sort by current selected column
+            Key::Char('s') => {
+                if self.sort_index == self.column_index {
+                    self.sort_descending = !self.sort_descending;
+                }
+                self.sort_index = self.column_index;
+                self.refresh();
+            }
+            _ => {}
+        }
+        self.table_state.select(Some(self.item_index));
+    }
+
+    /// Handles keyboard input of the listening ports view
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_listening_input(&mut self, key: Key) {
+        match key {
+            // Navigate by row
+            Key::Down if self.listening_index + 1 < self.listening.len() => {
+                self.listening_index += 1;
+            }
+            Key::Up if self.listening_index > 0 => {
+                self.listening_index -= 1;
+            }
+            _ => {}
+        }
+        self.listening_state.select(Some(self.listening_index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The kernel prints the address words in host byte order, the samples are taken from
+    // a little endian machine.
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parse_ipv4_address() {
+        let (address, port) = parse_address("0100007F:0016").unwrap();
+        assert_eq!(address, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(port, 22);
+
+        assert_eq!(parse_address("0100007F"), None);
+        assert_eq!(parse_address("0100007:0016"), None);
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parse_tcp6_sockets() {
+        let content = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 000080FE000000000000000001000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 12345 1 0000000000000000 100 0 0 10 0
+   1: 0000000000000000FFFF00000100007F:1F90 0000000000000000FFFF00000100007F:C350 01 00000010:00000020 00:00000000 00000000  1000        0 23456 1 0000000000000000 20 4 30 10 -1
+";
+        let sockets = parse_inet_sockets(content, Protocol::Tcp6);
+        assert_eq!(sockets.len(), 2);
+
+        let listen = &sockets[0];
+        assert_eq!(listen.local_address, "fe80::1");
+        assert_eq!(listen.get_local(), "[fe80::1]:22");
+        assert_eq!(listen.state, "LISTEN");
+        assert_eq!(listen.inode, 12345);
+        assert!(listen.is_listening());
+        assert!(!listen.is_bound_to_all());
+
+        // IPv4 mapped addresses are shown as IPv4
+        let mapped = &sockets[1];
+        assert_eq!(mapped.get_local(), "127.0.0.1:8080");
+        assert_eq!(mapped.get_remote(), "127.0.0.1:50000");
+        assert_eq!(mapped.state, "ESTABLISHED");
+        assert_eq!((mapped.tx_queue, mapped.rx_queue), (16, 32));
+        assert_eq!(mapped.uid, 1000);
+        assert!(!mapped.is_listening());
+    }
+
+    #[test]
+    fn parse_unix_listen_socket() {
+        let content = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 34567 /run/my app/control.sock
+0000000000000000: 00000003 00000000 00000000 0001 03 45678
+";
+        let sockets = parse_unix_sockets(content);
+        assert_eq!(sockets.len(), 2);
+
+        assert_eq!(sockets[0].state, "LISTEN");
+        assert_eq!(sockets[0].local_address, "/run/my app/control.sock");
+        assert_eq!(sockets[0].inode, 34567);
+
+        assert_eq!(sockets[1].state, "CONNECTED");
+        assert_eq!(sockets[1].local_address, "");
+    }
+
+    #[test]
+    fn look_up_user_names() {
+        assert_eq!(get_user_name(0).as_deref(), Some("root"));
+        assert_eq!(get_user_name(u32::MAX - 1), None);
+    }
+}
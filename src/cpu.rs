@@ -1,6 +1,7 @@
-use std::collections::VecDeque;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::sync::mpsc;
 use std::{io::BufRead, io::BufReader};
 use std::{thread, time};
@@ -11,8 +12,8 @@ use tui::{
     style::{Color, Modifier, Style},
     symbols,
     terminal::Frame,
-    text::Span,
-    widgets::{Axis, Block, Chart, Dataset, GraphType},
+    text::{Span, Spans},
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Paragraph},
 };
 
 use crate::util;
@@ -26,18 +27,18 @@ pub struct ProcStatRow {
     /// Name of the CPU
     pub cpu_name: String,
     /// Normal processes user mode
-    pub normal_proc_user_mode: u32,
+    pub normal_proc_user_mode: u64,
     /// Niced proccesses user mode
-    pub nice_proc_user_mode: u32,
+    pub nice_proc_user_mode: u64,
     /// Proccesses kernel mode
-    pub system_proc_kernel_mode: u32,
-    pub idle: u32,
+    pub system_proc_kernel_mode: u64,
+    pub idle: u64,
     /// waiting for I/O
-    pub iowait: u32,
+    pub iowait: u64,
     /// servicing interrupts
-    pub irq: u32,
+    pub irq: u64,
     /// servicing softirqs
-    pub softirq: u32,
+    pub softirq: u64,
 }
 
 impl ProcStatRow {
@@ -48,7 +49,7 @@ impl ProcStatRow {
     /// # Panic
     ///
     /// This function won't panic.
-    fn get_total_time(&self) -> u32 {
+    fn get_total_time(&self) -> u64 {
         self.normal_proc_user_mode
             + self.nice_proc_user_mode
             + self.system_proc_kernel_mode
@@ -85,10 +86,15 @@ fn calculate_cpu_utilization(previous: &ProcStatRow, current: &ProcStatRow) -> f
     let previous_total_elapsed = previous.get_total_time();
     let current_total_elapsed = current.get_total_time();
 
-    let total_delta = (current_total_elapsed - previous_total_elapsed) as f64;
-    let idle_delta = (current.idle - previous.idle) as f64;
+    // The deltas are ticks spent in each state since the previous measurement, so the
+    // utilization doesn't depend on the time between both measurements
+    let total_delta = current_total_elapsed.saturating_sub(previous_total_elapsed) as f64;
+    let idle_delta = current.idle.saturating_sub(previous.idle) as f64;
+    if total_delta <= 0.0 {
+        return 0.0;
+    }
     let utilization: f64 = 100.0 * (1.0 - idle_delta / total_delta);
-    utilization
+    utilization.clamp(0.0, 100.0)
 }
 
 /// Opens and returns a new file handle to the /proc/stat file.
@@ -97,13 +103,7 @@ fn calculate_cpu_utilization(previous: &ProcStatRow, current: &ProcStatRow) -> f
 ///
 /// This function won't panic.
 fn get_proc_stat_file_handle() -> Option<File> {
-    let file_handle = File::open("/proc/stat");
-
-    let file = match file_handle {
-        Ok(x) => Some(x),
-        Err(_) => None,
-    };
-    file
+    File::open("/proc/stat").ok()
 }
 
 /// Reads and parses the cpu utilization provided by /proc/stat
@@ -135,8 +135,6 @@ fn update_current_cpu_utilization(
 
             // We only care about cpu information, so discard other lines
             if row.starts_with("cpu") {
-                let mut field_counter = 0;
-
                 let mut columns = row.split_whitespace();
                 let current_cpu_name = match columns.next() {
                     Some(a) => a,
@@ -144,15 +142,9 @@ fn update_current_cpu_utilization(
                 };
 
                 // Store data temporarly into an array
-                let mut values: [u32; 10] = [0; 10];
-                for z in columns {
-                    let number: u32 = match z.trim().parse() {
-                        Err(_) => 0,
-                        Ok(n) => n,
-                    };
-
-                    values[field_counter] = number;
-                    field_counter += 1;
+                let mut values: [u64; 10] = [0; 10];
+                for (value, z) in values.iter_mut().zip(columns) {
+                    *value = z.trim().parse().unwrap_or_default();
                 }
                
                 // Create a new struct from the saved data
@@ -179,7 +171,7 @@ fn update_current_cpu_utilization(
                     };
 
                     let utilization = CpuUtilization {
-                        cpu_name: current_cpu_name.to_string(),
+                        cpu_name: current_stat.cpu_name.clone(),
                         utilization: calculate_cpu_utilization(&previous_stat, &current_stat),
                     };
                     result.push(utilization);
@@ -194,7 +186,8 @@ fn update_current_cpu_utilization(
 
 /// Initializes a thread to collect and send the cpu utilization each 0.5 seconds.
 ///
-/// Calculates current cpu utilization and sends the result to the receiver.
+/// Calculates current cpu utilization and sends the result to the receiver. The
+/// utilization is a ratio of ticks, so no timestamp is needed.
 ///
 /// # Panic
 ///
@@ -221,42 +214,219 @@ pub fn init_data_collection_thread() -> mpsc::Receiver<Vec<CpuUtilization>> {
     rx
 }
 
+/// Position of a logical cpu (hardware thread) inside the cpu topology
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpuTopology {
+    /// Id of the physical package (socket)
+    pub socket_id: usize,
+    /// Id of the physical core inside the socket
+    pub core_id: usize,
+}
+
+/// Reads a single numeric value of a file in /sys/devices/system/cpu/cpuX/topology/
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_topology_value(cpu: usize, name: &str) -> Option<usize> {
+    let path = format!("/sys/devices/system/cpu/cpu{}/topology/{}", cpu, name);
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Reads the topology of the given logical cpus.
+///
+/// CPUs without topology information (e.g. in some virtual machines) are treated as
+/// separate cores on socket 0.
+///
+/// See https://www.kernel.org/doc/html/latest/admin-guide/cputopology.html
+///
+/// # Arguments
+///
+/// * 'cpus' - ids of the logical cpus
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_cpu_topology(cpus: &[usize]) -> HashMap<usize, CpuTopology> {
+    cpus.iter()
+        .map(|&cpu| {
+            let topology = CpuTopology {
+                socket_id: read_topology_value(cpu, "physical_package_id").unwrap_or(0),
+                core_id: read_topology_value(cpu, "core_id").unwrap_or(cpu),
+            };
+            (cpu, topology)
+        })
+        .collect()
+}
+
+/// Defines how the cores are grouped in the chart.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CoreGrouping {
+    /// Every logical cpu is shown, SMT siblings share a color
+    Threads,
+    /// Logical cpus are aggregated per physical core
+    PhysicalCores,
+    /// Logical cpus are aggregated per socket
+    Sockets,
+}
+
+impl CoreGrouping {
+    /// Returns the next grouping mode
+    fn next(&self) -> CoreGrouping {
+        match *self {
+            CoreGrouping::Threads => CoreGrouping::PhysicalCores,
+            CoreGrouping::PhysicalCores => CoreGrouping::Sockets,
+            CoreGrouping::Sockets => CoreGrouping::Threads,
+        }
+    }
+
+    /// Returns the name of the grouping mode
+    pub fn get_name(&self) -> &str {
+        match *self {
+            CoreGrouping::Threads => "threads",
+            CoreGrouping::PhysicalCores => "cores",
+            CoreGrouping::Sockets => "sockets",
+        }
+    }
+}
+
+/// Defines how the cpu utilization is visualized.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CpuViewMode {
+    /// Overlaid line chart of the utilization history
+    Chart,
+    /// htop-like horizontal bar meter per core
+    Bars,
+    /// Grid of cores x time, colored by utilization
+    Heatmap,
+}
+
+impl CpuViewMode {
+    /// Returns the next view mode
+    fn next(&self) -> CpuViewMode {
+        match *self {
+            CpuViewMode::Chart => CpuViewMode::Bars,
+            CpuViewMode::Bars => CpuViewMode::Heatmap,
+            CpuViewMode::Heatmap => CpuViewMode::Chart,
+        }
+    }
+
+    /// Returns the name of the view mode, which is also used in the config file
+    pub fn get_name(&self) -> &str {
+        match *self {
+            CpuViewMode::Chart => "chart",
+            CpuViewMode::Bars => "bars",
+            CpuViewMode::Heatmap => "heatmap",
+        }
+    }
+
+    /// Returns the view mode by its name, unknown names default to the chart
+    pub fn get_by_name(name: &str) -> CpuViewMode {
+        match name {
+            "bars" => CpuViewMode::Bars,
+            "heatmap" => CpuViewMode::Heatmap,
+            _ => CpuViewMode::Chart,
+        }
+    }
+}
+
+/// Utilization history of a logical cpu or a group of logical cpus
+pub struct CoreSeries {
+    pub name: String,
+    pub color: Color,
+    pub values: Vec<f64>,
+}
+
+/// Number of most recent values used to determine the busiest cores
+const BUSY_WINDOW: usize = 10;
+
+/// Number of cores which are shown if the top-n mode is enabled
+const DEFAULT_TOP_N: usize = 8;
+
+/// Averages the utilization histories of multiple logical cpus.
+///
+/// The histories are aligned at the most recent value.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn average_histories(histories: &[&Vec<f64>]) -> Vec<f64> {
+    let len = histories.iter().map(|h| h.len()).min().unwrap_or(0);
+    (0..len)
+        .map(|i| {
+            let sum: f64 = histories.iter().map(|h| h[h.len() - len + i]).sum();
+            sum / histories.len() as f64
+        })
+        .collect()
+}
+
 pub struct CpuWidget {
     // Utilization data of different cores
     core_values: std::vec::Vec<Vec<f64>>,
+    // Logical cpu ids of the cores in core_values
+    core_ids: Vec<usize>,
+    // Topology of the logical cpus
+    topology: HashMap<usize, CpuTopology>,
     // Aggregated cpu utilization data
     cpu_values: std::vec::Vec<f64>,
     show_all_cores: bool,
+    // How the cores are grouped
+    grouping: CoreGrouping,
+    // Only show the n busiest cores / groups
+    top_n: Option<usize>,
+    // How the utilization is visualized
+    view_mode: CpuViewMode,
     dc_thread: mpsc::Receiver<Vec<CpuUtilization>>,
 }
 
 impl CpuWidget {
     /// Returns a new CpuWidget with default values and a new data thread.
     ///
+    /// # Arguments
+    ///
+    /// * 'view_mode' - the initial visualization, usually read from the config
+    ///
     /// # Panic
     ///
     /// This function won't panic.
-    pub fn new() -> Self {
+    pub fn new(view_mode: CpuViewMode) -> Self {
         Self {
             core_values: Vec::<Vec<f64>>::new(),
+            core_ids: Vec::new(),
+            topology: HashMap::new(),
             cpu_values: Vec::<f64>::new(),
             show_all_cores: true,
+            grouping: CoreGrouping::Threads,
+            top_n: None,
+            view_mode,
             dc_thread: init_data_collection_thread(),
         }
     }
 
     /// Updates the data which is used by the visualization.
     ///
+    /// All samples received since the last update are added to the history.
+    ///
     /// # Panic
     ///
     /// This function won't panic.
     pub fn update(&mut self) {
         // Recv data from the data collector thread
-        let cpu_stats = match self.dc_thread.try_recv() {
-            Ok(a) => a,
-            Err(_) => vec![],
-        };
+        while let Ok(cpu_stats) = self.dc_thread.try_recv() {
+            self.add_sample(cpu_stats);
+        }
+    }
 
+    /// Adds the utilization of one measurement to the history.
+    ///
+    /// # Arguments
+    ///
+    /// * 'cpu_stats' - utilization of all cpus and the aggregated utilization
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn add_sample(&mut self, cpu_stats: Vec<CpuUtilization>) {
         let mut counter = 0;
         for b in cpu_stats {
             // Aggregated cpu utilization value / total cpu utilization
@@ -281,6 +451,9 @@ impl CpuWidget {
                     // Creates new vec if no vec exists for a cpu core
                     self.core_values.push(Vec::new());
                     self.core_values[counter].push(b.utilization);
+                    self.core_ids
+                        .push(b.cpu_name[3..].parse().unwrap_or(counter));
+                    self.topology = read_cpu_topology(&self.core_ids);
                 }
                 // Increase counter since the next iteration will be a new cpu core
                 counter += 1
@@ -288,9 +461,90 @@ impl CpuWidget {
         }
     }
 
-    /// Draws cpu utilization graph in a given Rect.
+    /// Returns the utilization histories which are shown in the chart.
+    ///
+    /// The logical cpus are grouped based on the current grouping mode and sorted by
+    /// socket, physical core and SMT sibling. If the top-n mode is active, only the
+    /// busiest series are returned.
     ///
-    /// Each cpu cores is rendered in a different color.
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn get_series(&self) -> Vec<CoreSeries> {
+        // Group the logical cpus, the BTreeMap keeps the groups sorted by their position
+        let mut groups: BTreeMap<(usize, usize, usize), Vec<usize>> = BTreeMap::new();
+        for (i, cpu) in self.core_ids.iter().enumerate() {
+            let t = self.topology.get(cpu).copied().unwrap_or(CpuTopology {
+                socket_id: 0,
+                core_id: *cpu,
+            });
+            let key = match self.grouping {
+                CoreGrouping::Threads => (t.socket_id, t.core_id, *cpu),
+                CoreGrouping::PhysicalCores => (t.socket_id, t.core_id, 0),
+                CoreGrouping::Sockets => (t.socket_id, 0, 0),
+            };
+            groups.entry(key).or_default().push(i);
+        }
+
+        // Assign one color per physical core (threads and cores) or socket
+        let mut color_index: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        let mut series = Vec::new();
+        for ((socket, core, cpu), members) in groups {
+            let next_color = color_index.len();
+            let color = *color_index.entry((socket, core)).or_insert(next_color);
+
+            let name = match self.grouping {
+                CoreGrouping::Threads => format!("cpu{} s{}c{}", cpu, socket, core),
+                CoreGrouping::PhysicalCores => format!("s{}c{}", socket, core),
+                CoreGrouping::Sockets => format!("socket{}", socket),
+            };
+            let histories: Vec<&Vec<f64>> = members.iter().map(|&i| &self.core_values[i]).collect();
+
+            series.push(CoreSeries {
+                name,
+                color: util::get_color_by_scalar(color),
+                values: average_histories(&histories),
+            });
+        }
+
+        // Only keep the busiest series, based on the average of the most recent values
+        if let Some(n) = self.top_n {
+            let recent = |s: &CoreSeries| {
+                let window = &s.values[s.values.len().saturating_sub(BUSY_WINDOW)..];
+                window.iter().sum::<f64>() / window.len().max(1) as f64
+            };
+            series.sort_by(|a, b| recent(b).partial_cmp(&recent(a)).unwrap_or(Ordering::Equal));
+            series.truncate(n);
+        }
+
+        series
+    }
+
+    /// Returns the current view mode
+    pub fn get_view_mode(&self) -> CpuViewMode {
+        self.view_mode
+    }
+
+    /// Returns the series shown by the bar and heatmap views.
+    ///
+    /// If all cores are hidden, only the aggregated cpu utilization is returned.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn get_visible_series(&self) -> Vec<CoreSeries> {
+        if self.show_all_cores && !self.core_values.is_empty() {
+            self.get_series()
+        } else {
+            vec![CoreSeries {
+                name: "cpu".to_string(),
+                color: Color::White,
+                values: self.cpu_values.clone(),
+            }]
+        }
+    }
+
+    /// Draws the cpu utilization in a given Rect using the current view mode.
     ///
     /// # Arguments
     ///
@@ -302,36 +556,203 @@ impl CpuWidget {
     ///
     /// This function won't panic.
     pub fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        match self.view_mode {
+            CpuViewMode::Chart => self.draw_chart(f, rect, block),
+            CpuViewMode::Bars => self.draw_bars(f, rect, block),
+            CpuViewMode::Heatmap => self.draw_heatmap(f, rect, block),
+        }
+    }
+
+    /// Draws htop-like horizontal bar meters, one per core.
+    ///
+    /// If there are more cores than lines, the meters are split into multiple columns.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_bars<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        let series = self.get_visible_series();
+
+        let height = (inner.height as usize).max(1);
+        let columns = series.len().div_ceil(height);
+        let column_width = inner.width as usize / columns.max(1);
+        let name_width = series.iter().map(|s| s.name.len()).max().unwrap_or(0);
+
+        let mut lines = vec![Vec::new(); height.min(series.len())];
+        for (i, core) in series.iter().enumerate() {
+            let utilization = core.values.last().copied().unwrap_or_default();
+            let label = format!("{:5.1}%", utilization);
+
+            // name, space, brackets, label and a space between the columns
+            let bar_width = column_width.saturating_sub(name_width + 4 + label.len());
+            let filled = ((utilization / 100.0 * bar_width as f64).round() as usize).min(bar_width);
+
+            let line = &mut lines[i % height];
+            line.push(Span::styled(
+                format!("{:>width$} [", core.name, width = name_width),
+                Style::default().fg(core.color),
+            ));
+            line.push(Span::styled(
+                "|".repeat(filled),
+                Style::default().fg(util::get_color_by_utilization(utilization)),
+            ));
+            line.push(Span::raw(" ".repeat(bar_width - filled)));
+            line.push(Span::styled(
+                label,
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            line.push(Span::raw("] "));
+        }
+
+        let text: Vec<Spans> = lines.into_iter().map(Spans::from).collect();
+        f.render_widget(Paragraph::new(text).block(block), rect);
+    }
+
+    /// Draws a heatmap with one row per core and one column per measurement.
+    ///
+    /// The most recent measurement is on the right. If there are more cores than lines,
+    /// two cores share a line by using half blocks. If there are still too many cores,
+    /// neighbouring cores are averaged into one row, so no core is left out.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_heatmap<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        let mut series = self.get_visible_series();
+
+        let height = (inner.height as usize).max(1);
+        let cores_per_line = if series.len() > height { 2 } else { 1 };
+
+        // Average groups of cores which don't fit into the half block rows
+        let group_size = series.len().div_ceil(height * cores_per_line).max(1);
+        if group_size > 1 {
+            series = series
+                .chunks(group_size)
+                .map(|group| {
+                    let last = &group[group.len() - 1].name;
+                    let histories: Vec<&Vec<f64>> = group.iter().map(|s| &s.values).collect();
+                    CoreSeries {
+                        name: format!(
+                            "{}-{}",
+                            group[0].name,
+                            last.trim_start_matches(|c: char| !c.is_ascii_digit())
+                        ),
+                        color: group[0].color,
+                        values: average_histories(&histories),
+                    }
+                })
+                .collect();
+        }
+        let name_width = series.iter().map(|s| s.name.len()).max().unwrap_or(0);
+        let label_width = name_width * cores_per_line + cores_per_line;
+        let samples = (inner.width as usize).saturating_sub(label_width);
+
+        let mut text = Vec::new();
+        for chunk in series.chunks(cores_per_line) {
+            let label = chunk
+                .iter()
+                .map(|s| format!("{:>width$}", s.name, width = name_width))
+                .collect::<Vec<_>>()
+                .join("/");
+            let mut line = vec![Span::styled(
+                format!("{} ", label),
+                Style::default().fg(chunk[0].color),
+            )];
+
+            // Value of a core `offset` samples before the most recent one
+            let value_at = |s: &CoreSeries, offset: usize| {
+                if offset < s.values.len() {
+                    Some(s.values[s.values.len() - 1 - offset])
+                } else {
+                    None
+                }
+            };
+            for offset in (0..samples).rev() {
+                let upper = value_at(&chunk[0], offset);
+                let lower = chunk.get(1).and_then(|s| value_at(s, offset));
+                let color = |v: Option<f64>| match v {
+                    Some(v) => util::get_color_by_utilization(v),
+                    None => Color::Reset,
+                };
+
+                let span = if cores_per_line == 1 {
+                    Span::styled(" ", Style::default().bg(color(upper)))
+                } else {
+                    Span::styled(
+                        "\u{2580}",
+                        Style::default().fg(color(upper)).bg(color(lower)),
+                    )
+                };
+                line.push(span);
+            }
+            text.push(Spans::from(line));
+        }
+
+        f.render_widget(Paragraph::new(text).block(block), rect);
+    }
+
+    /// Draws cpu utilization graph in a given Rect.
+    ///
+    /// The cores are grouped by their topology, cores of the same physical core or socket
+    /// share a color.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_chart<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
         let mut datasets = Vec::new();
 
         // Temporary variable to store dataset data
         let mut values = Vec::new();
 
         // Draw all cores
-        if self.show_all_cores {
-            // Parse utilization data, so chart can be drawn
-            for core in &self.core_values {
-                let value = core
-                    .iter()
-                    .enumerate()
-                    .map(|(i, &x)| ((i as f64), x))
-                    .collect::<Vec<_>>();
-                values.push(value);
-            }
+        let series = if self.show_all_cores {
+            self.get_series()
+        } else {
+            vec![]
+        };
 
-            // Create dataset for each value
-            for i in 0..values.len() {
-                let color = util::get_color_by_scalar(i);
-
-                datasets.push(
-                    Dataset::default()
-                        .name(format!("cpu{}", i))
-                        .marker(symbols::Marker::Braille)
-                        .style(Style::default().fg(color))
-                        .graph_type(GraphType::Line)
-                        .data(&values[i]),
-                );
-            }
+        // Parse utilization data, so chart can be drawn
+        for core in &series {
+            let value = core
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| ((i as f64), x))
+                .collect::<Vec<_>>();
+            values.push(value);
+        }
+
+        // Create dataset for each value
+        for (core, value) in series.iter().zip(values.iter()) {
+            datasets.push(
+                Dataset::default()
+                    .name(core.name.clone())
+                    .marker(symbols::Marker::Braille)
+                    .style(Style::default().fg(core.color))
+                    .graph_type(GraphType::Line)
+                    .data(value),
+            );
         }
 
         // Add aggregated cpu utilization
@@ -369,7 +790,8 @@ impl CpuWidget {
 
     /// Handles the input for the widget.
     ///
-    /// The space bar toggles the show or hide all core feature.
+    /// The space bar toggles the show or hide all core feature, v cycles the view modes.
+    /// g cycles the grouping of the cores, t toggles the top-n mode and +/- change n.
     ///
     /// # Arguments
     ///
@@ -382,7 +804,34 @@ impl CpuWidget {
         match key {
             // Show or hide all cores in chart
             Key::Char(' ') => self.show_all_cores = !self.show_all_cores,
+            // Switch between chart, bars and heatmap
+            Key::Char('v') => self.view_mode = self.view_mode.next(),
+            // Group by threads, physical cores or sockets
+            Key::Char('g') => self.grouping = self.grouping.next(),
+            // Show only the busiest cores
+            Key::Char('t') => {
+                self.top_n = match self.top_n {
+                    Some(_) => None,
+                    None => Some(DEFAULT_TOP_N),
+                }
+            }
+            Key::Char('+') => self.top_n = self.top_n.map(|n| n + 1),
+            Key::Char('-') => self.top_n = self.top_n.map(|n| (n - 1).max(1)),
             _ => {}
         };
     }
+
+    /// Returns dynamic help text based on current widget state.
+    pub fn get_help_text(&self) -> String {
+        let mut text = format!(
+            ", v: view ({}), g: group by ({})",
+            self.view_mode.get_name(),
+            self.grouping.get_name()
+        );
+        match self.top_n {
+            Some(n) => text += &format!(", t: show all, +/-: top {}", n),
+            None => text += ", t: top n busiest",
+        }
+        text
+    }
 }
@@ -0,0 +1,470 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, read_dir};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    terminal::Frame,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use crate::util;
+
+/// Maximum number of scan events applied per update, keeps the UI responsive
+const EVENTS_PER_UPDATE: usize = 20000;
+
+/// Width of the usage bar in the table
+const BAR_WIDTH: usize = 10;
+
+/// Result of the scan of a single directory
+pub enum ScanEvent {
+    /// The files directly inside a directory
+    Directory {
+        /// Path relative to the scanned mount point
+        path: Vec<String>,
+        /// Allocated bytes of the directory itself
+        own_size: u64,
+        /// Names and allocated bytes of the files
+        files: Vec<(String, u64)>,
+    },
+    /// A directory couldn't be read, e.g. because of missing permissions
+    Error,
+    Finished,
+}
+
+/// A file or directory with the size of all its content
+#[derive(Debug, Default)]
+pub struct DirEntry {
+    /// Allocated bytes, like "du"
+    pub size: u64,
+    /// Number of files and directories inside
+    pub items: u64,
+    pub is_dir: bool,
+    pub children: BTreeMap<String, DirEntry>,
+}
+
+impl DirEntry {
+    /// Adds the result of a scanned directory to the tree.
+    ///
+    /// Missing parent directories are created, the size and item count of all parents
+    /// is increased, so a partly scanned tree always shows the sizes known so far.
+    ///
+    /// # Arguments
+    ///
+    /// * 'path' - path of the directory relative to this entry
+    /// * 'own_size' - allocated bytes of the directory itself
+    /// * 'files' - names and allocated bytes of the files in the directory
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn add_directory(&mut self, path: &[String], own_size: u64, files: Vec<(String, u64)>) {
+        let size = own_size + files.iter().map(|(_, s)| s).sum::<u64>();
+        let items = files.len() as u64 + 1;
+
+        let mut node = self;
+        for name in path {
+            node.size += size;
+            node.items += items;
+            node = node
+                .children
+                .entry(name.clone())
+                .or_insert_with(|| DirEntry {
+                    is_dir: true,
+                    ..Default::default()
+                });
+        }
+        node.size += size;
+        node.items += items - 1;
+        for (name, size) in files {
+            node.children.insert(
+                name,
+                DirEntry {
+                    size,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    /// Returns the entry at the given relative path.
+    pub fn get(&self, path: &[String]) -> Option<&DirEntry> {
+        path.iter()
+            .try_fold(self, |node, name| node.children.get(name))
+    }
+
+    /// Returns the children sorted by size, the largest first.
+    pub fn get_sorted_children(&self) -> Vec<(&String, &DirEntry)> {
+        let mut children: Vec<(&String, &DirEntry)> = self.children.iter().collect();
+        children.sort_by(|a, b| b.1.size.cmp(&a.1.size).then(a.0.cmp(b.0)));
+        children
+    }
+}
+
+/// Scans a directory tree and sends the result of each directory.
+///
+/// The scan doesn't follow symlinks and stays on the filesystem of the root, like
+/// "du -x". Hard linked files are only counted once. The scan stops if the receiver
+/// was dropped.
+///
+/// # Arguments
+///
+/// * 'root' - the directory to scan, usually a mount point
+/// * 'tx' - the sender for the scan events
+///
+/// # Panic
+///
+/// This function won't panic.
+fn scan(root: &Path, tx: &mpsc::Sender<ScanEvent>) {
+    let device = match fs::symlink_metadata(root) {
+        Ok(m) => m.dev(),
+        Err(_) => {
+            let _ = tx.send(ScanEvent::Error);
+            return;
+        }
+    };
+    let mut hard_links: HashSet<u64> = HashSet::new();
+    let mut stack: Vec<(PathBuf, Vec<String>)> = vec![(root.to_path_buf(), vec![])];
+
+    while let Some((dir, path)) = stack.pop() {
+        let own_size = fs::symlink_metadata(&dir)
+            .map(|m| m.blocks() * 512)
+            .unwrap_or_default();
+        let entries = match read_dir(&dir) {
+            Ok(x) => x,
+            Err(_) => {
+                if tx.send(ScanEvent::Error).is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        let mut files = Vec::new();
+        for entry in entries.filter_map(|e| e.ok()) {
+            let metadata = match entry.metadata() {
+                Ok(x) => x,
+                Err(_) => continue,
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            if metadata.is_dir() {
+                // other filesystems mounted below are skipped
+                if metadata.dev() == device {
+                    let mut child = path.clone();
+                    child.push(name);
+                    stack.push((entry.path(), child));
+                }
+            } else if metadata.nlink() <= 1 || hard_links.insert(metadata.ino()) {
+                files.push((name, metadata.blocks() * 512));
+            }
+        }
+
+        let event = ScanEvent::Directory {
+            path,
+            own_size,
+            files,
+        };
+        if tx.send(event).is_err() {
+            return;
+        }
+    }
+
+    let _ = tx.send(ScanEvent::Finished);
+}
+
+/// Initializes a thread which scans the given directory once.
+///
+/// # Arguments
+///
+/// * 'root' - the directory to scan
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_scan_thread(root: PathBuf) -> mpsc::Receiver<ScanEvent> {
+    let (tx, rx) = mpsc::channel();
+
+    // Thread for the scan, it ends when the scan is finished or the explorer was closed
+    thread::spawn(move || scan(&root, &tx));
+
+    rx
+}
+
+/// Stores the state data of the directory explorer.
+pub struct DirExplorer {
+    /// The scanned mount point
+    root_path: String,
+    /// Sizes of the scanned files and directories
+    root: DirEntry,
+    /// Path of the shown directory relative to the mount point
+    path: Vec<String>,
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Name of the selected entry, the first entry is selected if not set. The rows are
+    /// reordered while the scan runs, so the selection follows the name.
+    selected: Option<String>,
+    /// Whether the scan is still running
+    scanning: bool,
+    /// Number of directories which couldn't be read
+    errors: u64,
+    /// Used to receive the results of the scan thread.
+    scan_thread: mpsc::Receiver<ScanEvent>,
+}
+
+impl DirExplorer {
+    /// Creates a new explorer and starts the scan of the mount point.
+    ///
+    /// # Arguments
+    ///
+    /// * 'mount_point' - the directory to scan
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new(mount_point: &str) -> Self {
+        Self {
+            root_path: mount_point.to_string(),
+            root: DirEntry {
+                is_dir: true,
+                ..Default::default()
+            },
+            path: vec![],
+            table_state: TableState::default(),
+            selected: None,
+            scanning: true,
+            errors: 0,
+            scan_thread: init_scan_thread(PathBuf::from(mount_point)),
+        }
+    }
+
+    /// Adds the new results of the scan thread to the tree.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        for event in self.scan_thread.try_iter().take(EVENTS_PER_UPDATE) {
+            match event {
+                ScanEvent::Directory {
+                    path,
+                    own_size,
+                    files,
+                } => self.root.add_directory(&path, own_size, files),
+                ScanEvent::Error => self.errors += 1,
+                ScanEvent::Finished => self.scanning = false,
+            }
+        }
+    }
+
+    /// Returns the index of the selected entry in the children sorted by size.
+    fn get_selected_index(&self, children: &[(&String, &DirEntry)]) -> usize {
+        self.selected
+            .as_ref()
+            .and_then(|selected| children.iter().position(|(n, _)| *n == selected))
+            .unwrap_or(0)
+    }
+
+    /// Returns the absolute path of the shown directory.
+    fn get_current_path(&self) -> String {
+        let mut path = PathBuf::from(&self.root_path);
+        path.extend(&self.path);
+        path.to_string_lossy().to_string()
+    }
+
+    /// Draws the content of the shown directory, sorted by size.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect) {
+        let dir = self.root.get(&self.path).unwrap_or(&self.root);
+
+        let mut title = format!(
+            "{} ({} in {} items",
+            self.get_current_path(),
+            util::to_humanreadable(dir.size as usize),
+            dir.items
+        );
+        if self.errors > 0 {
+            title += &format!(", {} unreadable", self.errors);
+        }
+        title += match self.scanning {
+            true => ", scanning...)",
+            false => ")",
+        };
+
+        let block = Block::default()
+            .style(Style::default().fg(Color::Yellow))
+            .title(title)
+            .borders(Borders::ALL);
+
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header = Row::new(vec!["Size", "Share", "", "Items", "Name"])
+            .style(header_style)
+            .height(1);
+
+        // Populate rows of table
+        let children = dir.get_sorted_children();
+        self.table_state
+            .select(Some(self.get_selected_index(&children)));
+        let rows = children.into_iter().map(|(name, entry)| {
+            let share = match dir.size {
+                0 => 0.0,
+                total => entry.size as f64 / total as f64 * 100.0,
+            };
+            let filled = (share / 100.0 * BAR_WIDTH as f64).round() as usize;
+            let name = match entry.is_dir {
+                true => format!("{}/", name),
+                false => name.clone(),
+            };
+            Row::new(vec![
+                Cell::from(format!(
+                    "{: >10}",
+                    util::to_humanreadable(entry.size as usize)
+                )),
+                Cell::from(format!("{: >5.1}%", share)),
+                Cell::from(format!(
+                    "[{}{}]",
+                    "#".repeat(filled),
+                    " ".repeat(BAR_WIDTH - filled)
+                ))
+                .style(Style::default().fg(util::get_color_by_utilization(share))),
+                Cell::from(match entry.is_dir {
+                    true => format!("{: >8}", entry.items),
+                    false => String::new(),
+                }),
+                Cell::from(name),
+            ])
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(block)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(BAR_WIDTH as u16 + 2),
+                Constraint::Length(8),
+                Constraint::Min(10),
+            ]);
+        f.render_widget(Clear, rect);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        let dir = self.root.get(&self.path).unwrap_or(&self.root);
+        let children = dir.get_sorted_children();
+        let index = self.get_selected_index(&children);
+
+        match key {
+            // Navigate by row
+            Key::Down if index + 1 < children.len() => {
+                self.selected = Some(children[index + 1].0.clone());
+            }
+            Key::Up if index > 0 => {
+                self.selected = Some(children[index - 1].0.clone());
+            }
+            // Open the selected directory
+            Key::Right | Key::Char('\n') => {
+                if let Some((name, entry)) = children.get(index) {
+                    if entry.is_dir {
+                        self.path.push(name.to_string());
+                        self.selected = None;
+                    }
+                }
+            }
+            // Go to the parent directory and select the directory we came from
+            Key::Left | Key::Backspace => {
+                self.selected = self.path.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn add_directories() {
+        let mut root = DirEntry {
+            is_dir: true,
+            ..Default::default()
+        };
+        root.add_directory(&[], 4096, vec![("a".to_string(), 100)]);
+        // a subdirectory scanned before its parent
+        root.add_directory(
+            &path(&["usr", "lib"]),
+            4096,
+            vec![("x".to_string(), 10), ("y".to_string(), 20)],
+        );
+        root.add_directory(&path(&["usr"]), 4096, vec![]);
+
+        // sizes include the directory itself, the items don't
+        assert_eq!(root.size, 4096 + 100 + 4096 + 30 + 4096);
+        assert_eq!(root.items, 1 + 3 + 1);
+
+        let usr = root.get(&path(&["usr"])).unwrap();
+        assert!(usr.is_dir);
+        assert_eq!(usr.size, 4096 + 30 + 4096);
+        assert_eq!(usr.items, 3);
+
+        let lib = root.get(&path(&["usr", "lib"])).unwrap();
+        assert_eq!(lib.size, 4096 + 30);
+        assert_eq!(lib.items, 2);
+        assert!(!lib.children["x"].is_dir);
+
+        assert!(root.get(&path(&["usr", "missing"])).is_none());
+    }
+
+    #[test]
+    fn sort_children_by_size() {
+        let mut root = DirEntry::default();
+        root.add_directory(
+            &[],
+            0,
+            vec![
+                ("b".to_string(), 10),
+                ("c".to_string(), 20),
+                ("a".to_string(), 10),
+            ],
+        );
+        let names: Vec<&str> = root
+            .get_sorted_children()
+            .into_iter()
+            .map(|(n, _)| n.as_str())
+            .collect();
+        assert_eq!(names, vec!["c", "a", "b"]);
+    }
+}
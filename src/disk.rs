@@ -1,18 +1,76 @@
-use std::process::Command;
-use std::str;
-use std::sync::mpsc;
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::CString;
+use std::fs::File;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
 use std::thread;
-use std::time;
+use std::time::{self, Duration, Instant};
+use std::{io::BufRead, io::BufReader};
 use termion::event::Key;
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     terminal::Frame,
-    widgets::{Block, Cell, Row, Table},
+    widgets::{Block, Cell, Row, Table, TableState},
 };
 
+use crate::dirscan::DirExplorer;
+use crate::util;
+
+const PROC_MOUNTINFO: &str = "/proc/self/mountinfo";
+
+/// Inode usage in percent from which a filesystem is highlighted
+const INODE_USAGE_THRESHOLD: f64 = 90.0;
+
+/// Number of columns of the partitions table
+const COLUMN_COUNT: usize = 10;
+
+/// Widths of the columns by column id, the mount point gets the remaining space
+const COLUMN_WIDTHS: [u16; COLUMN_COUNT] = [9, 9, 6, 6, 4, 5, 7, 8, 8, 24];
+
+/// Column ids by importance, the last ones are hidden first on smaller screens
+const COLUMN_PRIORITY: [usize; COLUMN_COUNT] = [0, 4, 5, 6, 8, 1, 2, 3, 7, 9];
+
+/// Space between two columns
+const COLUMN_SPACING: u16 = 2;
+
+/// Time span over which the growth of the used space is measured
+const FILL_RATE_WINDOW: Duration = Duration::from_secs(600);
+
+/// Minimal time span of samples before a growth rate is reported
+const FILL_RATE_MIN_SPAN: Duration = Duration::from_secs(30);
+
+/// Default time to full in hours from which a filesystem is highlighted
+pub const DEFAULT_FULL_ALERT_HOURS: f64 = 24.0;
+
+/// Filesystem types which only provide kernel interfaces instead of storage
+const PSEUDO_FS_TYPES: [&str; 20] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "sysfs",
+    "tracefs",
+];
+
 // equals the "df"-command output
 #[derive(Debug, Default)]
 pub struct DiskInfo {
@@ -22,99 +80,483 @@ pub struct DiskInfo {
     pub available: usize,
     pub used_percentage: String,
     pub mountpoint: String,
+    pub fs_type: String,
+    /// Mount options, e.g. "rw,relatime"
+    pub options: String,
+    /// Number of inodes, 0 if the filesystem has no fixed number of inodes (e.g. btrfs)
+    pub inodes_total: u64,
+    pub inodes_used: u64,
+    pub inodes_free: u64,
+    /// Growth of the used space in KiB per second, None until enough samples were taken
+    pub fill_rate: Option<f64>,
 }
 
-/// Get current disk usage
-/// 
-/// This function returns a Vector containing a DiskInfo for each disk.
-/// 
-/// See ( https://en.wikipedia.org/wiki/Df_(Unix) ) for mor informations on the "df" command.
-/// 
+impl DiskInfo {
+    /// Returns the share of used inodes in percent, None if the filesystem has no inode limit.
+    pub fn inode_usage(&self) -> Option<f64> {
+        match self.inodes_total {
+            0 => None,
+            total => Some(self.inodes_used as f64 / total as f64 * 100.0),
+        }
+    }
+
+    /// Returns the estimated seconds until the filesystem is full, None if it doesn't grow.
+    pub fn time_to_full(&self) -> Option<f64> {
+        match self.fill_rate {
+            Some(rate) if rate > 0.0 => Some(self.available as f64 / rate),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the filesystem will be full within the given number of seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * 'limit' - the alert limit in seconds
+    pub fn is_filling_up(&self, limit: f64) -> bool {
+        self.time_to_full().is_some_and(|t| t <= limit)
+    }
+}
+
+/// Tracks the used space of the filesystems to calculate their growth rate.
+#[derive(Default)]
+struct FillRateTracker {
+    /// Time and used KiB of each mount point, the newest sample is the last one
+    samples: HashMap<String, VecDeque<(Instant, usize)>>,
+}
+
+impl FillRateTracker {
+    /// Adds the current usage of the filesystems and sets their fill rate.
+    ///
+    /// The rate is the slope of a linear regression over the samples of the last
+    /// FILL_RATE_WINDOW, so single spikes like a temporary file don't dominate it.
+    ///
+    /// # Arguments
+    ///
+    /// * 'disks' - the current usage, the fill rate is set in place
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn update(&mut self, disks: &mut [DiskInfo]) {
+        let now = Instant::now();
+
+        // Forget unmounted filesystems
+        self.samples
+            .retain(|mount_point, _| disks.iter().any(|d| &d.mountpoint == mount_point));
+
+        for disk in disks.iter_mut() {
+            let samples = self.samples.entry(disk.mountpoint.clone()).or_default();
+            samples.push_back((now, disk.used));
+            while let Some((time, _)) = samples.front() {
+                if now.duration_since(*time) <= FILL_RATE_WINDOW {
+                    break;
+                }
+                samples.pop_front();
+            }
+
+            let first = match samples.front() {
+                Some((time, _)) if now.duration_since(*time) >= FILL_RATE_MIN_SPAN => *time,
+                _ => continue,
+            };
+            let points: Vec<(f64, f64)> = samples
+                .iter()
+                .map(|(time, used)| (time.duration_since(first).as_secs_f64(), *used as f64))
+                .collect();
+            disk.fill_rate = Some(linear_slope(&points));
+        }
+    }
+}
+
+/// Returns the slope of the least squares line through the points.
+///
 /// # Panic
-/// 
-/// This function will panic if the "df" output is not ok or the "df" output could not be parsed.
-pub fn get_disks_usage() -> Vec<DiskInfo> {
-    let mut disk_array = Vec::new();
-    // execute "df"
-    let mut df_command = Command::new("df");
-    let df_output = match df_command.output() {
-        Ok(x) => x,
-        _ => panic!("Could not read df output"),
-    };
+///
+/// This function won't panic.
+fn linear_slope(points: &[(f64, f64)]) -> f64 {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (covariance, variance) = points.iter().fold((0.0, 0.0), |(c, v), (x, y)| {
+        (c + (x - mean_x) * (y - mean_y), v + (x - mean_x).powi(2))
+    });
+    match variance > 0.0 {
+        true => covariance / variance,
+        false => 0.0,
+    }
+}
+
+/// Formats an estimated time to full, e.g. "45m" or "3d".
+///
+/// # Panic
+///
+/// This function won't panic.
+fn format_time_to_full(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    match seconds {
+        0..=59 => "<1m".to_string(),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=172_799 => format!("{}h", seconds / 3600),
+        172_800..=31_535_999 => format!("{}d", seconds / 86400),
+        _ => ">1y".to_string(),
+    }
+}
 
-    // parse string from utf8 Vec
-    let df_output_string = match str::from_utf8(&df_output.stdout) {
-        Ok(v) => v,
-        Err(e) => panic!("Invalid UTF-8 sequence: {}", e),
+/// Formats a fill rate in KiB per second, e.g. "+1.2M/s".
+///
+/// # Panic
+///
+/// This function won't panic.
+fn format_fill_rate(rate: f64) -> String {
+    match rate {
+        x if x.abs() < 1.0 => "0".to_string(),
+        x if x > 0.0 => format!("+{}/s", calc_disk_size(x as usize)),
+        x => format!("-{}/s", calc_disk_size(-x as usize)),
+    }
+}
+
+/// A mounted filesystem, read from /proc/self/mountinfo
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MountInfo {
+    /// "major:minor" of the device, bind mounts share the id of the original mount, all
+    /// subvolumes of a btrfs filesystem share the same id as well
+    pub device_id: String,
+    /// Mounted device or pseudo name, e.g. "/dev/sda1" or "tmpfs"
+    pub source: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    /// Mount options, e.g. "rw,relatime"
+    pub options: String,
+}
+
+/// A rule selecting mounts for the partitions table
+#[derive(Debug, Clone, PartialEq)]
+pub enum MountRule {
+    /// Matches the filesystem type exactly, e.g. "type:tmpfs"
+    FsType(String),
+    /// Matches a mount point and everything below it, e.g. "mount:/run"
+    MountPrefix(String),
+    /// Matches sources starting with the given value, e.g. "device:/dev/loop"
+    Device(String),
+}
+
+impl MountRule {
+    /// Parses a rule in the format "type:<fs type>", "mount:<path>" or "device:<source>".
+    ///
+    /// Returns None for unknown rule types or empty values.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let (kind, value) = rule.trim().split_once(':')?;
+        let value = value.trim().to_string();
+        if value.is_empty() {
+            return None;
+        }
+        match kind.trim() {
+            "type" => Some(MountRule::FsType(value)),
+            "mount" => Some(MountRule::MountPrefix(value)),
+            "device" => Some(MountRule::Device(value)),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the mount matches the rule.
+    pub fn matches(&self, mount: &MountInfo) -> bool {
+        match self {
+            MountRule::FsType(fs_type) => mount.fs_type == *fs_type,
+            MountRule::MountPrefix(path) => {
+                let path = path.trim_end_matches('/');
+                match mount.mount_point.strip_prefix(path) {
+                    Some(rest) => rest.is_empty() || rest.starts_with('/'),
+                    None => false,
+                }
+            }
+            MountRule::Device(source) => mount.source.starts_with(source.as_str()),
+        }
+    }
+}
+
+/// Include and exclude rules for the mounts shown in the partitions table
+///
+/// If include rules exist, a mount has to match at least one of them.
+/// Mounts matching an exclude rule are never shown.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MountFilter {
+    include: Vec<MountRule>,
+    exclude: Vec<MountRule>,
+}
+
+impl MountFilter {
+    /// Creates a filter from comma separated rules, see [MountRule::parse].
+    ///
+    /// Invalid rules are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * 'include' - rules of mounts to show, e.g. "type:ext4,type:xfs"
+    /// * 'exclude' - rules of mounts to hide, e.g. "mount:/snap,device:/dev/loop"
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new(include: &str, exclude: &str) -> Self {
+        let parse = |rules: &str| rules.split(',').filter_map(MountRule::parse).collect();
+        Self {
+            include: parse(include),
+            exclude: parse(exclude),
+        }
+    }
+
+    /// Returns whether the mount is shown.
+    pub fn matches(&self, mount: &MountInfo) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|r| r.matches(mount)))
+            && !self.exclude.iter().any(|r| r.matches(mount))
+    }
+}
+
+/// Replaces the octal escape sequences of a mountinfo field.
+///
+/// The kernel escapes spaces, tabs, newlines and backslashes in paths as "\040",
+/// "\011", "\012" and "\134". Invalid sequences are kept unchanged.
+///
+/// # Arguments
+///
+/// * 'field' - A field of /proc/self/mountinfo
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).and_then(|digits| {
+            let digits = std::str::from_utf8(digits).ok()?;
+            u8::from_str_radix(digits, 8).ok()
+        });
+        match (bytes[i], octal) {
+            (b'\\', Some(c)) => {
+                result.push(c);
+                i += 4;
+            }
+            (b, _) => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+/// Parses the content of /proc/self/mountinfo.
+///
+/// Each line has the format "id parent major:minor root mount_point options
+/// [optional fields...] - fs_type source super_options". The number of optional
+/// fields varies, so the fields behind the mount point are located by the "-" separator.
+/// Malformed lines are skipped.
+///
+/// See https://man7.org/linux/man-pages/man5/proc.5.html
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_mountinfo<R: BufRead>(reader: R) -> Vec<MountInfo> {
+    let mut mounts = Vec::new();
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+
+        let fields: Vec<&str> = line.split(' ').collect();
+        let separator = match fields.iter().skip(6).position(|f| *f == "-") {
+            Some(x) => x + 6,
+            None => continue,
+        };
+        let (fs_type, source) = match (fields.get(separator + 1), fields.get(separator + 2)) {
+            (Some(fs_type), Some(source)) => (fs_type, source),
+            _ => continue,
+        };
+
+        mounts.push(MountInfo {
+            device_id: fields[2].to_string(),
+            source: unescape_mount_field(source),
+            mount_point: unescape_mount_field(fields[4]),
+            fs_type: unescape_mount_field(fs_type),
+            options: unescape_mount_field(fields[5]),
+        });
+    }
+
+    mounts
+}
+
+/// Returns the device number of a mount point as reported by stat, like "df" uses it.
+///
+/// Unlike the device id in mountinfo, each btrfs subvolume has its own device number.
+/// Returns None if stat fails (e.g. the mount point isn't accessible).
+///
+/// # Panic
+///
+/// This function won't panic.
+// the stat field types differ between platforms
+#[allow(clippy::unnecessary_cast)]
+fn get_device_number(mount_point: &str) -> Option<u64> {
+    let path = CString::new(mount_point).ok()?;
+    // SAFETY: stat only writes into the provided struct, which is plain old data
+    let mut stat: libc::stat = unsafe { mem::zeroed() };
+    if unsafe { libc::stat(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.st_dev as u64)
+}
+
+/// Calls statvfs for a mount point.
+///
+/// Returns a DiskInfo with the sizes in 1K-blocks like "df" and the inode counts,
+/// or None if statvfs fails (e.g. the mount point isn't accessible).
+///
+/// # Panic
+///
+/// This function won't panic.
+// the statvfs field types differ between platforms
+#[allow(clippy::unnecessary_cast)]
+fn get_filesystem_usage(mount_point: &str) -> Option<DiskInfo> {
+    let path = CString::new(mount_point).ok()?;
+    // SAFETY: statvfs only writes into the provided struct, which is plain old data
+    let mut stat: libc::statvfs = unsafe { mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let to_kib = |blocks| (blocks as u64 * stat.f_frsize as u64 / 1024) as usize;
+    let (total, used, available) = (
+        to_kib(stat.f_blocks),
+        to_kib(stat.f_blocks.saturating_sub(stat.f_bfree)),
+        to_kib(stat.f_bavail),
+    );
+    // "df" rounds the percentage up and ignores reserved blocks
+    let used_percentage = match used + available {
+        0 => "-".to_string(),
+        x => format!("{}%", (used * 100).div_ceil(x)),
     };
 
-    // add disks to array
-    for line in df_output_string.lines() {
-
-        if line.starts_with("/dev/") {
-            let mut sliced_line = line.split_whitespace();
-            // create new DiskInfo while iterating through a line
-            // has to be changed when the output of the "df" command changes its order
-            let disk_info = DiskInfo {
-                filesystem: match sliced_line.next() {
-                    Some(x) => x.replace("/dev", "").to_string(),
-                    _ => "".to_string(),
-                },
-                // maybe usecase for unwarp_or_default?
-                total: match sliced_line.next() {
-                    Some(x) => match x.parse() {
-                        Ok(x) => x,
-                        _ => 0,
-                    },
-                    _ => 0,
-                },
-                used: match sliced_line.next() {
-                    Some(x) => match x.parse() {
-                        Ok(x) => x,
-                        _ => 0,
-                    },
-                    _ => 0,
-                },
-                available: match sliced_line.next() {
-                    Some(x) => match x.parse() {
-                        Ok(x) => x,
-                        _ => 0,
-                    },
-                    _ => 0,
-                },
-                used_percentage: match sliced_line.next() {
-                    Some(x) => x.to_string(),
-                    _ => "".to_string(),
-                },
-                mountpoint: match sliced_line.next() {
-                    Some(x) => x.to_string(),
-                    _ => "".to_string(),
-                },
-            };
+    Some(DiskInfo {
+        total,
+        used,
+        available,
+        used_percentage,
+        inodes_total: stat.f_files as u64,
+        inodes_used: stat.f_files.saturating_sub(stat.f_ffree) as u64,
+        inodes_free: stat.f_ffree as u64,
+        ..Default::default()
+    })
+}
 
-            disk_array.push(disk_info);
+/// Get current disk usage
+///
+/// This function returns a Vector containing a DiskInfo for each mounted filesystem
+/// selected by the filter. The mounts are read from "/proc/self/mountinfo" and the usage
+/// is queried by statvfs. Like "df", a filesystem mounted multiple times (e.g. bind mounts)
+/// is only listed once with its shortest mount point. Mounts are compared by the device
+/// number of the mount point, so btrfs subvolumes are listed separately.
+///
+/// Pseudo filesystems are filesystems of a type in PSEUDO_FS_TYPES or without any blocks.
+///
+/// See ( https://en.wikipedia.org/wiki/Df_(Unix) ) for mor informations on the "df" command.
+///
+/// # Arguments
+///
+/// * 'filter' - include and exclude rules for the mounts
+/// * 'show_pseudo' - whether pseudo filesystems like proc or sysfs are included
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn get_disks_usage(filter: &MountFilter, show_pseudo: bool) -> Vec<DiskInfo> {
+    let mounts = match File::open(PROC_MOUNTINFO) {
+        Ok(file) => parse_mountinfo(BufReader::new(file)),
+        Err(_) => return vec![],
+    };
+
+    // keep the first mount of each filesystem, or a shorter one
+    let mut filesystems: Vec<MountInfo> = Vec::new();
+    let mut indices: HashMap<u64, usize> = HashMap::new();
+    let selected = mounts.into_iter().filter(|m| {
+        filter.matches(m) && (show_pseudo || !PSEUDO_FS_TYPES.contains(&m.fs_type.as_str()))
+    });
+    for mount in selected {
+        // stat is only called for selected mounts, it may block like statvfs
+        let device = match get_device_number(&mount.mount_point) {
+            Some(x) => x,
+            None => continue,
+        };
+        match indices.get(&device) {
+            Some(&i) if filesystems[i].mount_point.len() > mount.mount_point.len() => {
+                filesystems[i] = mount;
+            }
+            Some(_) => {}
+            None => {
+                indices.insert(device, filesystems.len());
+                filesystems.push(mount);
+            }
         }
     }
 
-    disk_array
+    // statvfs is only called for selected mounts, it may block on unreachable network filesystems
+    filesystems
+        .into_iter()
+        .filter_map(|mount| {
+            let usage = get_filesystem_usage(&mount.mount_point)?;
+            if !show_pseudo && usage.total == 0 {
+                return None;
+            }
+            Some(DiskInfo {
+                filesystem: mount.source.replace("/dev", ""),
+                mountpoint: mount.mount_point,
+                fs_type: mount.fs_type,
+                options: mount.options,
+                ..usage
+            })
+        })
+        .collect()
 }
 
 /// Initializes a thread to collect and send the disk usage eacht 0.5 seconds.
+///
+/// The thread keeps the recent usage of each filesystem to calculate its fill rate.
+///
+/// # Arguments
+///
+/// * 'filter' - include and exclude rules for the mounts
+/// * 'show_pseudo' - whether pseudo filesystems are included, can be changed while the thread runs
 /// 
 /// # Panic
 /// 
 /// This function won't panic.
-pub fn init_data_collection_thread() -> mpsc::Receiver<Vec<DiskInfo>> {
+pub fn init_data_collection_thread(
+    filter: MountFilter,
+    show_pseudo: Arc<AtomicBool>,
+) -> mpsc::Receiver<Vec<DiskInfo>> {
     let (tx, rx) = mpsc::channel();
     let dur = time::Duration::from_millis(500);
 
     // Thread for the data collection
-    thread::spawn(move || loop {
-        let m = get_disks_usage();
+    thread::spawn(move || {
+        let mut tracker = FillRateTracker::default();
+        loop {
+            let mut m = get_disks_usage(&filter, show_pseudo.load(AtomicOrdering::Relaxed));
+            tracker.update(&mut m);
 
-        let _ = tx.send(m);
+            let _ = tx.send(m);
 
-        thread::sleep(dur);
+            thread::sleep(dur);
+        }
     });
 
     rx
@@ -145,7 +587,7 @@ pub fn calc_disk_size(disk_size: usize) -> String {
 
     // calculate the Size to match the gnome system monitor -> decimal base
     while size > 1000.0 {
-        size = size / 1000.0;
+        size /= 1000.0;
         count += 1
     }
 
@@ -156,23 +598,119 @@ pub fn calc_disk_size(disk_size: usize) -> String {
 }
 
 pub struct DiskWidget {
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected item.
     item_index: usize,
+    /// Index of column to sort by.
+    sort_index: usize,
+    /// Index of current selected column.
+    column_index: usize,
+    /// Sort the column descending or ascending.
+    sort_descending: bool,
+    /// Ids of the columns which fit into the last drawn area.
+    visible_columns: Vec<usize>,
     disk_info: std::vec::Vec<DiskInfo>,
     dc_thread: mpsc::Receiver<Vec<DiskInfo>>,
+    /// Show the inode columns instead of the size columns
+    show_inodes: bool,
+    /// Tells the data collection thread whether pseudo filesystems are shown
+    show_pseudo: Arc<AtomicBool>,
+    /// Time to full in seconds from which a filesystem is highlighted
+    full_alert: f64,
+    /// Directory explorer of the selected filesystem, shown over the bottom area if set
+    explorer: Option<DirExplorer>,
 }
 
 impl DiskWidget {
     /// Returns a new DiskWidget with default values and a new data thread.
+    ///
+    /// # Arguments
+    ///
+    /// * 'filter' - include and exclude rules for the shown mounts
+    /// * 'full_alert_hours' - time to full in hours from which a filesystem is highlighted
     /// 
     /// # Panic
     /// 
     /// This funxtion won't panic.
-    pub fn new() -> Self {
-        Self {
+    pub fn new(filter: MountFilter, full_alert_hours: f64) -> Self {
+        let show_pseudo = Arc::new(AtomicBool::new(false));
+        let mut a = Self {
+            table_state: TableState::default(),
             item_index: 0,
+            sort_index: 5,
+            column_index: 5,
+            sort_descending: false,
+            visible_columns: (0..COLUMN_COUNT).collect(),
             disk_info: Default::default(),
-            dc_thread: init_data_collection_thread(),
-        }
+            dc_thread: init_data_collection_thread(filter, show_pseudo.clone()),
+            show_inodes: false,
+            show_pseudo,
+            full_alert: full_alert_hours * 3600.0,
+            explorer: None,
+        };
+        a.table_state.select(Some(0));
+        a
+    }
+
+    /// Sorts the filesystems ascending or descending by the current selected column.
+    ///
+    /// The size columns are sorted by the inode values while the inodes are shown.
+    fn sort(&mut self) {
+        let sort_index = self.sort_index;
+        let sort_descending = self.sort_descending;
+        let show_inodes = self.show_inodes;
+        self.disk_info.sort_by(|a, b| {
+            let s = match (sort_index, show_inodes) {
+                (0, _) => a.filesystem.cmp(&b.filesystem),
+                (1, false) => a.available.cmp(&b.available),
+                (2, false) => a.used.cmp(&b.used),
+                (3, false) => a.total.cmp(&b.total),
+                (4, false) => get_usage(a)
+                    .partial_cmp(&get_usage(b))
+                    .unwrap_or(Ordering::Equal),
+                (1, true) => a.inodes_free.cmp(&b.inodes_free),
+                (2, true) => a.inodes_used.cmp(&b.inodes_used),
+                (3, true) => a.inodes_total.cmp(&b.inodes_total),
+                (4, true) => a
+                    .inode_usage()
+                    .partial_cmp(&b.inode_usage())
+                    .unwrap_or(Ordering::Equal),
+                (5, _) => a.mountpoint.cmp(&b.mountpoint),
+                // filesystems which don't grow are never full
+                (6, _) => a
+                    .time_to_full()
+                    .unwrap_or(f64::INFINITY)
+                    .partial_cmp(&b.time_to_full().unwrap_or(f64::INFINITY))
+                    .unwrap_or(Ordering::Equal),
+                (7, _) => a
+                    .fill_rate
+                    .partial_cmp(&b.fill_rate)
+                    .unwrap_or(Ordering::Equal),
+                (8, _) => a.fs_type.cmp(&b.fs_type),
+                (9, _) => a.options.cmp(&b.options),
+                _ => Ordering::Equal,
+            };
+            if sort_descending {
+                Ordering::reverse(s)
+            } else {
+                s
+            }
+        });
+    }
+
+    /// Selects the given row, the selection is kept inside the table.
+    fn select(&mut self, index: usize) {
+        self.item_index = index.min(self.disk_info.len().saturating_sub(1));
+        self.table_state.select(Some(self.item_index));
+    }
+
+    /// Returns the number of filesystems which will be full within the alert limit.
+    pub fn get_alert_count(&self) -> usize {
+        self.disk_info
+            .iter()
+            .filter(|d| d.is_filling_up(self.full_alert))
+            .count()
     }
     /// Updates the disk_info of the DiskWidget
     /// 
@@ -184,8 +722,45 @@ impl DiskWidget {
 
         let result = self.dc_thread.try_recv();
 
-        if result.is_ok() {
-            self.disk_info = result.unwrap();
+        if let Ok(disk_info) = result {
+            self.disk_info = disk_info;
+            self.sort();
+
+            // Keep the selection inside the table
+            self.select(self.item_index);
+        }
+
+        if let Some(explorer) = &mut self.explorer {
+            explorer.update();
+        }
+    }
+
+    /// Returns whether the directory explorer is open.
+    pub fn is_exploring(&self) -> bool {
+        self.explorer.is_some()
+    }
+
+    /// Returns the help text of the open directory explorer.
+    pub fn get_help_text(&self) -> &str {
+        match self.explorer {
+            Some(_) => ", ENTER/right: open directory, left: parent directory, CTRL-x: close",
+            None => "",
+        }
+    }
+
+    /// Draws the directory explorer if it is open.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the explorer gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw_explorer<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect) {
+        if let Some(explorer) = &mut self.explorer {
+            explorer.draw(f, rect);
         }
     }
     /// Draws disk information in a given Rect.
@@ -204,89 +779,297 @@ impl DiskWidget {
     /// 
     /// This function draws the DiskWidget based on its disk_info.
     /// Call the update function before to get current information.
-    pub fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
         //draw disk info TODO: divide into own function
-        let header_cells = ["Partition", "Available", "In Use", "Total", "Used", "Mount"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::White)));
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_names = match self.show_inodes {
+            true => [
+                "Partition",
+                "IFree",
+                "IUsed",
+                "Inodes",
+                "IUse",
+                "Mount",
+                "Full in",
+                "Growth",
+                "Type",
+                "Options",
+            ],
+            false => [
+                "Partition",
+                "Available",
+                "In Use",
+                "Total",
+                "Used",
+                "Mount",
+                "Full in",
+                "Growth",
+                "Type",
+                "Options",
+            ],
+        };
+        let columns = visible_columns(rect.width);
+        let header_cells = columns.iter().map(|&i| {
+            let h = header_names[i];
+            if i == self.column_index {
+                Cell::from(h).style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+            } else {
+                Cell::from(h).style(Style::default().fg(Color::White))
+            }
+        });
         let header = Row::new(header_cells).height(1);
 
-        let rows = self.disk_info.iter().skip(self.item_index).map(|disk| {
-            let mut cells = Vec::new();
-            cells.push(Cell::from(disk.filesystem.clone()));
-            cells.push(Cell::from(calc_disk_size(disk.available)));
-            cells.push(Cell::from(calc_disk_size(disk.used)));
-            cells.push(Cell::from(calc_disk_size(disk.total)));
-            cells.push(Cell::from(disk.used_percentage.clone()));
-            cells.push(Cell::from(disk.mountpoint.clone()));
-            Row::new(cells)
+        let rows = self.disk_info.iter().map(|disk| {
+            // filesystems running out of inodes are highlighted in both views
+            let inode_usage = disk.inode_usage();
+            let inode_style = match inode_usage {
+                Some(x) if x >= INODE_USAGE_THRESHOLD => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            };
+            let count = |value: u64| util::to_humanreadable_count(value as f64);
+
+            // filesystems which will be full soon are highlighted in both views
+            let filling_up = disk.is_filling_up(self.full_alert);
+            let name_style = match filling_up {
+                true => Style::default().fg(Color::Red),
+                false => inode_style,
+            };
+            let full_in = Cell::from(match disk.time_to_full() {
+                Some(x) => format_time_to_full(x),
+                None => "-".to_string(),
+            })
+            .style(match filling_up {
+                true => Style::default().fg(Color::Red),
+                false => Style::default(),
+            });
+            let growth = Cell::from(match disk.fill_rate {
+                Some(x) => format_fill_rate(x),
+                None => "-".to_string(),
+            });
+
+            let cells = if self.show_inodes {
+                vec![
+                    Cell::from(disk.filesystem.clone()).style(name_style),
+                    Cell::from(count(disk.inodes_free)),
+                    Cell::from(count(disk.inodes_used)),
+                    Cell::from(count(disk.inodes_total)),
+                    Cell::from(match inode_usage {
+                        Some(x) => format!("{:.0}%", x.ceil()),
+                        None => "-".to_string(),
+                    })
+                    .style(inode_style),
+                    Cell::from(disk.mountpoint.clone()),
+                    full_in,
+                    growth,
+                    Cell::from(disk.fs_type.clone()),
+                    Cell::from(disk.options.clone()),
+                ]
+            } else {
+                vec![
+                    Cell::from(disk.filesystem.clone()).style(name_style),
+                    Cell::from(calc_disk_size(disk.available)),
+                    Cell::from(calc_disk_size(disk.used)),
+                    Cell::from(calc_disk_size(disk.total)),
+                    Cell::from(disk.used_percentage.clone()),
+                    Cell::from(disk.mountpoint.clone()),
+                    full_in,
+                    growth,
+                    Cell::from(disk.fs_type.clone()),
+                    Cell::from(disk.options.clone()),
+                ]
+            };
+            Row::new(columns.iter().map(|&i| cells[i].clone()))
         });
-        let sizing = &size_columns(rect.width);
+        let sizing: Vec<Constraint> = columns
+            .iter()
+            .map(|&i| match i {
+                5 => Constraint::Min(COLUMN_WIDTHS[i]),
+                _ => Constraint::Length(COLUMN_WIDTHS[i]),
+            })
+            .collect();
         let table = Table::new(rows)
             .header(header)
             .block(block)
-            .widths(sizing)
-            .column_spacing(2);
-        f.render_widget(table, rect);
+            .highlight_style(selected_style)
+            .widths(&sizing)
+            .column_spacing(COLUMN_SPACING);
+        f.render_stateful_widget(table, rect, &mut self.table_state);
+
+        // Keep the selected column visible if the area got smaller
+        if !columns.contains(&self.column_index) {
+            self.column_index = columns.first().copied().unwrap_or_default();
+        }
+        self.visible_columns = columns;
     }
     /// Input Handler for the DiskWidget.
     /// 
-    /// Enables Table to scroll up and down.
+    /// Enables the selection of rows and columns and sorting by the selected column.
+    /// The input is passed to the directory explorer while it is open.
     pub fn handle_input(&mut self, key: Key) {
+        if let Some(explorer) = &mut self.explorer {
+            // Close the explorer, this also stops the scan
+            match key {
+                Key::Ctrl('x') => self.explorer = None,
+                _ => explorer.handle_input(key),
+            }
+            return;
+        }
+
         match key {
-            Key::Down => {
-                if self.item_index < self.disk_info.len() - 1 {
-                    self.item_index += 1;
+            // Navigate by row
+            Key::Down => self.select(self.item_index + 1),
+            Key::Up => self.select(self.item_index.saturating_sub(1)),
+            // Navigate by the visible columns
+            Key::Right => {
+                let columns = &self.visible_columns;
+                if let Some(i) = columns.iter().position(|&c| c == self.column_index) {
+                    self.column_index = columns.get(i + 1).copied().unwrap_or(self.column_index);
                 }
             }
-            Key::Up => {
-                if self.item_index > 0 {
-                    self.item_index -= 1;
+            Key::Left => {
+                let columns = &self.visible_columns;
+                if let Some(i) = columns.iter().position(|&c| c == self.column_index) {
+                    self.column_index = columns[i.saturating_sub(1)];
                 }
             }
+            // Sort by current selected column
+            Key::Char('s') => {
+                if self.sort_index == self.column_index {
+                    self.sort_descending = !self.sort_descending;
+                }
+
+                self.sort_index = self.column_index;
+                self.sort();
+            }
+            // Explore the directories of the selected filesystem
+            Key::Char('\n') => {
+                self.explorer = self
+                    .disk_info
+                    .get(self.item_index)
+                    .map(|d| DirExplorer::new(&d.mountpoint));
+            }
+            // Switch between size and inode columns
+            Key::Char('i') => {
+                self.show_inodes = !self.show_inodes;
+                self.sort();
+            }
+            // Show or hide pseudo filesystems, takes effect with the next measurement
+            Key::Char('a') => {
+                let show_pseudo = !self.show_pseudo.load(AtomicOrdering::Relaxed);
+                self.show_pseudo.store(show_pseudo, AtomicOrdering::Relaxed);
+            }
             _ => {}
         };
     }
 }
 
-/// Adjust tablesize to screen resulting in less details on smaller screens.
-fn size_columns(area_width: u16) -> Vec<Constraint> {
-    let width = area_width - 2;
-    if width >= 39 + 10 {
-        vec![
-            Constraint::Length(9),
-            Constraint::Length(9),
-            Constraint::Length(6),
-            Constraint::Length(6),
-            Constraint::Length(4),
-            Constraint::Min(5),
-        ]
-    } else if width >= 34 + 8 {
-        vec![
-            Constraint::Length(9),
-            Constraint::Length(9),
-            Constraint::Length(6),
-            Constraint::Length(6),
-            Constraint::Length(4),
-        ]
-    } else if width >= 30 + 6 {
-        vec![
-            Constraint::Length(9),
-            Constraint::Length(9),
-            Constraint::Length(6),
-            Constraint::Length(6),
-        ]
-    } else if width >= 24 + 4 {
-        vec![
-            Constraint::Length(9),
-            Constraint::Length(9),
-            Constraint::Length(6),
-        ]
-    } else if width >= 18 + 2 {
-        vec![Constraint::Percentage(50), Constraint::Percentage(50)]
-    } else if width >= 9 {
-        vec![Constraint::Length(9)]
-    } else {
-        vec![]
+/// Returns the used share of the space in percent, 0 for filesystems without blocks.
+fn get_usage(disk: &DiskInfo) -> f64 {
+    match disk.total {
+        0 => 0.0,
+        total => disk.used as f64 / total as f64 * 100.0,
+    }
+}
+
+/// Returns the ids of the columns which fit into the area, in the order they are drawn.
+///
+/// Columns are added by their priority as long as they fit, so on smaller screens the
+/// time to full and the filesystem type are shown instead of the less important sizes.
+///
+/// # Arguments
+///
+/// * 'area_width' - width of the table including the borders
+///
+/// # Panic
+///
+/// This function won't panic.
+fn visible_columns(area_width: u16) -> Vec<usize> {
+    let width = area_width.saturating_sub(2);
+    let mut used = 0;
+    let mut columns: Vec<usize> = COLUMN_PRIORITY
+        .iter()
+        .copied()
+        .take_while(|&i| {
+            let needed = match used {
+                0 => COLUMN_WIDTHS[i],
+                _ => COLUMN_WIDTHS[i] + COLUMN_SPACING,
+            };
+            used += needed;
+            used <= width
+        })
+        .collect();
+    columns.sort_unstable();
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_octal_sequences() {
+        assert_eq!(unescape_mount_field(r"/mnt/my\040disk"), "/mnt/my disk");
+        assert_eq!(unescape_mount_field(r"a\011b\012c\134d"), "a\tb\nc\\d");
+        // invalid or truncated sequences are kept
+        assert_eq!(unescape_mount_field(r"/mnt/a\09"), r"/mnt/a\09");
+        assert_eq!(unescape_mount_field(r"/mnt/a\04"), r"/mnt/a\04");
+        assert_eq!(unescape_mount_field(r"/mnt/a\"), r"/mnt/a\");
+    }
+
+    #[test]
+    fn parse_optional_fields() {
+        let content = "\
+22 1 8:2 / / rw,relatime shared:1 - ext4 /dev/sda2 rw,errors=remount-ro
+23 22 0:21 / /proc rw,nosuid - proc proc rw
+24 22 0:30 /home /home rw,relatime shared:5 master:2 propagate_from:1 - btrfs /dev/sdb1 rw
+25 22 8:2 / /mnt/usb\\040stick rw - vfat /dev/sdc1 rw
+";
+        let mounts = parse_mountinfo(content.as_bytes());
+        assert_eq!(mounts.len(), 4);
+
+        assert_eq!(mounts[0].device_id, "8:2");
+        assert_eq!(mounts[0].source, "/dev/sda2");
+        assert_eq!(mounts[0].mount_point, "/");
+        assert_eq!(mounts[0].fs_type, "ext4");
+        assert_eq!(mounts[0].options, "rw,relatime");
+
+        // no optional fields
+        assert_eq!(mounts[1].fs_type, "proc");
+        assert_eq!(mounts[1].source, "proc");
+
+        // multiple optional fields
+        assert_eq!(mounts[2].mount_point, "/home");
+        assert_eq!(mounts[2].fs_type, "btrfs");
+        assert_eq!(mounts[2].source, "/dev/sdb1");
+
+        assert_eq!(mounts[3].mount_point, "/mnt/usb stick");
+    }
+
+    #[test]
+    fn columns_by_priority() {
+        // the disk box of an 80 column terminal
+        assert_eq!(visible_columns(35), vec![0, 4, 5, 6]);
+        assert_eq!(visible_columns(44), vec![0, 4, 5, 6, 8]);
+        assert_eq!(visible_columns(106), (0..COLUMN_COUNT).collect::<Vec<_>>());
+        assert_eq!(visible_columns(105).last(), Some(&8));
+        assert!(visible_columns(2).is_empty());
+        assert!(visible_columns(0).is_empty());
+    }
+
+    #[test]
+    fn skip_truncated_lines() {
+        let content = "\
+22 1 8:2 / / rw,relatime shared:1
+23 22 0:21 / /proc rw,nosuid - proc
+24 22 0:22 /
+25 22 0:23 / /sys rw - sysfs sysfs rw
+";
+        let mounts = parse_mountinfo(content.as_bytes());
+        assert_eq!(mounts.len(), 1);
+        assert_eq!(mounts[0].mount_point, "/sys");
+        assert!(parse_mountinfo("".as_bytes()).is_empty());
     }
 }
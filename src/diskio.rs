@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::mpsc;
+use std::{io::BufRead, io::BufReader};
+use std::{thread, time, time::Instant};
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    terminal::Frame,
+    text::Span,
+    widgets::{Axis, Block, Cell, Chart, Dataset, GraphType, Row, Table, TableState},
+};
+
+use crate::util::{self, Sample};
+
+const PROC_DISKSTATS: &str = "/proc/diskstats";
+
+/// The kernel counts sectors in units of 512 bytes, independent of the device
+const SECTOR_SIZE: u64 = 512;
+
+/// Number of measurements in the throughput history
+const HISTORY_LENGTH: usize = 300;
+
+/// Counters of a block device, read from /proc/diskstats
+///
+/// See https://www.kernel.org/doc/html/latest/admin-guide/iostats.html
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiskStatsRow {
+    pub name: String,
+    /// Completed reads
+    pub reads: u64,
+    pub sectors_read: u64,
+    /// Milliseconds spent reading
+    pub read_ticks: u64,
+    /// Completed writes
+    pub writes: u64,
+    pub sectors_written: u64,
+    /// Milliseconds spent writing
+    pub write_ticks: u64,
+    /// Requests currently in flight
+    pub in_flight: u64,
+    /// Milliseconds the device was busy
+    pub io_ticks: u64,
+    /// Milliseconds weighted by the number of requests in flight
+    pub time_in_queue: u64,
+}
+
+/// I/O activity of a block device between two measurements, like "iostat -x"
+#[derive(Default, Debug, Clone)]
+pub struct DiskIo {
+    pub name: String,
+    /// Whether the device is a partition of another device
+    pub is_partition: bool,
+    /// Reads per second
+    pub read_iops: f64,
+    /// Writes per second
+    pub write_iops: f64,
+    /// Bytes read per second
+    pub read_bytes: f64,
+    /// Bytes written per second
+    pub write_bytes: f64,
+    /// Average time of a read request in milliseconds
+    pub read_latency: f64,
+    /// Average time of a write request in milliseconds
+    pub write_latency: f64,
+    /// Average number of requests in the queue
+    pub queue_depth: f64,
+    /// Share of the time the device was busy in percent
+    pub utilization: f64,
+}
+
+/// Parses the content of /proc/diskstats.
+///
+/// Each line has the format "major minor name reads reads_merged sectors_read read_ticks
+/// writes writes_merged sectors_written write_ticks in_flight io_ticks time_in_queue ...".
+/// Newer kernels append discard and flush counters, which are ignored.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_diskstats<R: BufRead>(reader: R) -> Vec<DiskStatsRow> {
+    let mut rows = Vec::new();
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 14 {
+            continue;
+        }
+        let value = |i: usize| columns[i].parse().unwrap_or_default();
+
+        rows.push(DiskStatsRow {
+            name: columns[2].to_string(),
+            reads: value(3),
+            sectors_read: value(5),
+            read_ticks: value(6),
+            writes: value(7),
+            sectors_written: value(9),
+            write_ticks: value(10),
+            in_flight: value(11),
+            io_ticks: value(12),
+            time_in_queue: value(13),
+        });
+    }
+
+    rows
+}
+
+/// Reads /proc/diskstats.
+///
+/// Devices which never did any I/O (e.g. unused loop devices) are skipped.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_diskstats() -> Vec<DiskStatsRow> {
+    let mut rows = match File::open(PROC_DISKSTATS) {
+        Ok(file) => parse_diskstats(BufReader::new(file)),
+        Err(_) => return vec![],
+    };
+    rows.retain(|r| r.reads + r.writes > 0);
+    rows
+}
+
+/// Calculates the I/O activity of the block devices based on two measurements.
+///
+/// Devices without previous measurement get zero rates.
+///
+/// # Arguments
+///
+/// * 'previous' - previous counters mapped by device name
+/// * 'current' - current counters
+/// * 'elapsed' - elapsed time between both measurements in seconds
+///
+/// # Panic
+///
+/// This function won't panic.
+fn calculate_io(
+    previous: &HashMap<String, DiskStatsRow>,
+    current: &[DiskStatsRow],
+    elapsed: f64,
+) -> Vec<DiskIo> {
+    current
+        .iter()
+        .map(|row| {
+            // whole disks are listed in /sys/block, partitions aren't
+            let mut io = DiskIo {
+                name: row.name.clone(),
+                is_partition: !Path::new("/sys/block").join(&row.name).exists(),
+                ..Default::default()
+            };
+
+            let old = match previous.get(&row.name) {
+                Some(old) if elapsed > 0.0 => old,
+                _ => return io,
+            };
+            let rate =
+                |value: fn(&DiskStatsRow) -> u64| util::rate(value(old), value(row), elapsed);
+            let delta = |value: fn(&DiskStatsRow) -> u64| value(row).saturating_sub(value(old));
+            let latency = |ticks: u64, requests: u64| match requests {
+                0 => 0.0,
+                n => ticks as f64 / n as f64,
+            };
+
+            let reads = delta(|r| r.reads);
+            let writes = delta(|r| r.writes);
+            io.read_iops = rate(|r| r.reads);
+            io.write_iops = rate(|r| r.writes);
+            io.read_bytes = rate(|r| r.sectors_read) * SECTOR_SIZE as f64;
+            io.write_bytes = rate(|r| r.sectors_written) * SECTOR_SIZE as f64;
+            io.read_latency = latency(delta(|r| r.read_ticks), reads);
+            io.write_latency = latency(delta(|r| r.write_ticks), writes);
+            // the ticks are milliseconds
+            io.queue_depth = rate(|r| r.time_in_queue) / 1000.0;
+            io.utilization = (rate(|r| r.io_ticks) / 1000.0 * 100.0).min(100.0);
+            io
+        })
+        .collect()
+}
+
+/// Initializes a thread to collect and send the I/O activity each second.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_data_collection_thread() -> mpsc::Receiver<Sample<Vec<DiskIo>>> {
+    let (tx, rx) = mpsc::channel();
+    let dur = time::Duration::from_millis(1000);
+
+    let mut previous: HashMap<String, DiskStatsRow> = HashMap::new();
+    let mut previous_time = Instant::now();
+
+    // Thread for the data collection
+    thread::spawn(move || loop {
+        let rows = Sample {
+            time: Instant::now(),
+            data: read_diskstats(),
+        };
+        let elapsed = rows.elapsed_since(previous_time);
+        previous_time = rows.time;
+
+        let io = calculate_io(&previous, &rows.data, elapsed);
+        previous = rows.data.into_iter().map(|r| (r.name.clone(), r)).collect();
+
+        let _ = tx.send(Sample {
+            time: rows.time,
+            data: io,
+        });
+
+        thread::sleep(dur);
+    });
+
+    rx
+}
+
+/// Stores the state data of the widget.
+pub struct DiskIoWidget {
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected item.
+    item_index: usize,
+    /// Show partitions in addition to whole devices.
+    show_partitions: bool,
+    /// I/O activity of the shown devices.
+    devices: Vec<DiskIo>,
+    /// Read and written bytes per second of all devices, the newest value is the last one.
+    history: HashMap<String, Vec<(f64, f64)>>,
+    /// Used to receive data from the thread which reads the disk statistics.
+    dc_thread: mpsc::Receiver<Sample<Vec<DiskIo>>>,
+}
+
+impl DiskIoWidget {
+    /// Creates new disk I/O widget with default values.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new() -> Self {
+        let mut a = Self {
+            table_state: TableState::default(),
+            item_index: 0,
+            show_partitions: false,
+            devices: vec![],
+            history: HashMap::new(),
+            dc_thread: init_data_collection_thread(),
+        };
+        a.table_state.select(Some(0));
+        a
+    }
+
+    /// Retrieves information from the update thread and store the new data in the widget.
+    ///
+    /// The throughput history is kept for all devices, also for hidden partitions. All
+    /// measurements received since the last update are added to the history.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        // Recv data from the data collector thread
+        while let Ok(sample) = self.dc_thread.try_recv() {
+            let mut devices = sample.data;
+            for d in &devices {
+                let history = self.history.entry(d.name.clone()).or_default();
+                history.push((d.read_bytes, d.write_bytes));
+                if history.len() > HISTORY_LENGTH {
+                    history.remove(0);
+                }
+            }
+            // Forget removed devices
+            self.history
+                .retain(|name, _| devices.iter().any(|d| &d.name == name));
+
+            if !self.show_partitions {
+                devices.retain(|d| !d.is_partition);
+            }
+            self.devices = devices;
+
+            // Keep the selection inside the table
+            if self.item_index >= self.devices.len() {
+                self.item_index = self.devices.len().saturating_sub(1);
+                self.table_state.select(Some(self.item_index));
+            }
+        }
+    }
+
+    /// Draws a table of the I/O activity and the throughput history of the selected device.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(inner);
+
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header = Row::new(vec![
+            "Device", "r/s", "w/s", "Read/s", "Write/s", "r_await", "w_await", "aqu-sz", "Util",
+        ])
+        .style(header_style)
+        .height(1);
+
+        // Populate rows of table
+        let rows = self.devices.iter().map(|d| {
+            let throughput =
+                |bytes: f64| format!("{: >10}", util::to_humanreadable(bytes as usize));
+            Row::new(vec![
+                Cell::from(d.name.clone()),
+                Cell::from(format!("{: >8.1}", d.read_iops)),
+                Cell::from(format!("{: >8.1}", d.write_iops)),
+                Cell::from(throughput(d.read_bytes)),
+                Cell::from(throughput(d.write_bytes)),
+                Cell::from(format!("{: >7.2}", d.read_latency)),
+                Cell::from(format!("{: >7.2}", d.write_latency)),
+                Cell::from(format!("{: >6.2}", d.queue_depth)),
+                Cell::from(format!("{: >5.1}%", d.utilization))
+                    .style(Style::default().fg(util::get_color_by_utilization(d.utilization))),
+            ])
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ]);
+        f.render_stateful_widget(table, chunks[0], &mut self.table_state);
+
+        self.draw_chart(f, chunks[1]);
+    }
+
+    /// Draws the read and write throughput history of the selected device.
+    ///
+    /// The y-axis is scaled to the highest value in the history.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_chart<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let device = match self.devices.get(self.item_index) {
+            Some(x) => x,
+            None => return,
+        };
+        let history = match self.history.get(&device.name) {
+            Some(x) => x,
+            None => return,
+        };
+
+        let read: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .map(|(i, (r, _))| (i as f64, *r))
+            .collect();
+        let write: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .map(|(i, (_, w))| (i as f64, *w))
+            .collect();
+        let max = history.iter().fold(1.0, |m, (r, w)| r.max(*w).max(m));
+
+        let datasets = vec![
+            Dataset::default()
+                .name(format!(
+                    "read {}/s",
+                    util::to_humanreadable(device.read_bytes as usize)
+                ))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Green))
+                .graph_type(GraphType::Line)
+                .data(&read),
+            Dataset::default()
+                .name(format!(
+                    "write {}/s",
+                    util::to_humanreadable(device.write_bytes as usize)
+                ))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Red))
+                .graph_type(GraphType::Line)
+                .data(&write),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(format!("Throughput of {}", device.name)))
+            .x_axis(Axis::default().bounds([0.0, HISTORY_LENGTH as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(vec![
+                        Span::styled("0", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            util::to_humanreadable(max as usize) + "/s",
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                    ])
+                    .bounds([0.0, max]),
+            )
+            .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+        f.render_widget(chart, rect);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        match key {
+            // Navigate by row
+            Key::Down if self.item_index + 1 < self.devices.len() => {
+                self.item_index += 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            Key::Up if self.item_index > 0 => {
+                self.item_index -= 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            // Show or hide partitions, takes effect with the next measurement
+            Key::Char('p') => {
+                self.show_partitions = !self.show_partitions;
+                if !self.show_partitions {
+                    self.devices.retain(|d| !d.is_partition);
+                    self.item_index = self.item_index.min(self.devices.len().saturating_sub(1));
+                    self.table_state.select(Some(self.item_index));
+                }
+            }
+            _ => {}
+        }
+    }
+}
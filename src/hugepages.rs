@@ -0,0 +1,87 @@
+use std::fs::{self, read_dir};
+
+const SYS_HUGEPAGES: &str = "/sys/kernel/mm/hugepages";
+const THP_ENABLED: &str = "/sys/kernel/mm/transparent_hugepage/enabled";
+
+/// Usage of an explicit hugepage pool, read from /sys/kernel/mm/hugepages
+///
+/// There is one pool per supported hugepage size.
+/// See https://www.kernel.org/doc/html/latest/admin-guide/mm/hugetlbpage.html
+#[derive(Default, Debug, Clone)]
+pub struct HugePagePool {
+    /// Size of a single page in KiB
+    pub page_size: u64,
+    /// Number of pages in the pool
+    pub total: u64,
+    /// Number of pages not yet allocated
+    pub free: u64,
+    /// Number of pages reserved but not yet allocated
+    pub reserved: u64,
+    /// Number of pages allocated above the pool size
+    pub surplus: u64,
+}
+
+impl HugePagePool {
+    /// Returns the memory of the allocated pages in KiB.
+    pub fn used_size(&self) -> u64 {
+        self.total.saturating_sub(self.free) * self.page_size
+    }
+}
+
+/// Reads the explicit hugepage pools of all supported page sizes.
+///
+/// The pool directories are named "hugepages-<size>kB".
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_hugepage_pools() -> Vec<HugePagePool> {
+    let dirs = match read_dir(SYS_HUGEPAGES) {
+        Ok(x) => x,
+        Err(_) => return vec![],
+    };
+
+    let mut pools: Vec<HugePagePool> = dirs
+        .filter_map(|d| d.ok())
+        .filter_map(|d| {
+            let name = d.file_name().into_string().ok()?;
+            let page_size = name
+                .strip_prefix("hugepages-")?
+                .strip_suffix("kB")?
+                .parse()
+                .ok()?;
+            let read = |attribute: &str| -> u64 {
+                fs::read_to_string(d.path().join(attribute))
+                    .ok()
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or_default()
+            };
+
+            Some(HugePagePool {
+                page_size,
+                total: read("nr_hugepages"),
+                free: read("free_hugepages"),
+                reserved: read("resv_hugepages"),
+                surplus: read("surplus_hugepages"),
+            })
+        })
+        .collect();
+    pools.sort_unstable_by_key(|p| p.page_size);
+    pools
+}
+
+/// Returns the transparent hugepage mode ("always", "madvise" or "never").
+///
+/// Returns an empty string if the kernel has no transparent hugepage support.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_thp_mode() -> String {
+    let modes = fs::read_to_string(THP_ENABLED).unwrap_or_default();
+    modes
+        .split_whitespace()
+        .find_map(|m| m.strip_prefix('[').and_then(|m| m.strip_suffix(']')))
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,632 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::sync::mpsc;
+use std::{io::BufRead, io::BufReader};
+use std::{thread, time, time::Instant};
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+};
+
+use crate::util::{self, Sample};
+
+const PROC_INTERRUPTS: &str = "/proc/interrupts";
+const PROC_SOFTIRQS: &str = "/proc/softirqs";
+
+/// Defines whether a row belongs to a hardware interrupt or a softirq.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InterruptKind {
+    Hard,
+    Soft,
+}
+
+impl InterruptKind {
+    /// Returns the short name which is displayed in the table
+    fn get_name(&self) -> &str {
+        match *self {
+            InterruptKind::Hard => "irq",
+            InterruptKind::Soft => "soft",
+        }
+    }
+}
+
+/// Represents one row of /proc/interrupts or /proc/softirqs
+///
+/// See https://www.kernel.org/doc/html/latest/filesystems/proc.html#proc-interrupts
+#[derive(Clone, Debug)]
+pub struct InterruptRow {
+    /// IRQ number or name (e.g. "24", "LOC", "NET_RX")
+    pub name: String,
+    pub kind: InterruptKind,
+    /// Interrupt controller, trigger and device name(s)
+    pub description: String,
+    /// Number of interrupts per CPU since boot, a single value for rows which aren't
+    /// counted per CPU
+    pub counts: Vec<u64>,
+    /// Whether the row contains one counter per CPU
+    pub per_cpu: bool,
+}
+
+/// Stores the interrupt rates of a single interrupt line
+#[derive(Clone, Debug)]
+pub struct InterruptInfo {
+    pub name: String,
+    pub kind: InterruptKind,
+    pub description: String,
+    /// Number of interrupts on all CPUs since boot
+    pub total: u64,
+    /// Interrupts per second on all CPUs
+    pub total_rate: f64,
+    /// Interrupts per second per CPU, empty for rows which aren't counted per CPU
+    pub cpu_rates: Vec<f64>,
+}
+
+/// Result of one measurement, sent by the data collection thread
+#[derive(Default, Debug)]
+pub struct InterruptStats {
+    /// CPU names as listed in the header row (offline CPUs are omitted by the kernel)
+    pub cpu_names: Vec<String>,
+    pub interrupts: Vec<InterruptInfo>,
+}
+
+/// Parses the content of /proc/interrupts or /proc/softirqs.
+///
+/// The first line contains the CPU names. Every other line contains the name of the interrupt,
+/// one counter per CPU and an optional description. Some rows (e.g. "ERR" and "MIS") only
+/// contain a single counter without description.
+///
+/// /proc/interrupts lists the online CPUs, /proc/softirqs all possible CPUs. The counters
+/// of both files are only comparable by the CPU names.
+///
+/// # Arguments
+///
+/// * 'reader' - content of the file
+/// * 'kind' - whether the content belongs to hardware interrupts or softirqs
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_interrupts<R: BufRead>(
+    reader: R,
+    kind: InterruptKind,
+) -> (Vec<String>, Vec<InterruptRow>) {
+    let mut lines = reader.lines();
+
+    let cpu_names: Vec<String> = match lines.next() {
+        Some(Ok(header)) => header
+            .split_whitespace()
+            .map(|c| c.to_lowercase())
+            .collect(),
+        _ => return (vec![], vec![]),
+    };
+
+    let mut rows = Vec::new();
+
+    for line in lines {
+        let row = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+
+        let mut columns = row.split_whitespace();
+        let name = match columns.next() {
+            Some(x) => x.trim_end_matches(':').to_string(),
+            None => continue,
+        };
+
+        // Read one counter per CPU, the remaining columns describe the interrupt
+        let mut counts = Vec::with_capacity(cpu_names.len());
+        let mut description = Vec::new();
+        for column in columns {
+            if counts.len() < cpu_names.len() && description.is_empty() {
+                if let Ok(x) = column.parse::<u64>() {
+                    counts.push(x);
+                    continue;
+                }
+            }
+            description.push(column);
+        }
+
+        // Softirqs are always counted per CPU, hardware rows without description aren't
+        let per_cpu = counts.len() == cpu_names.len()
+            && (kind == InterruptKind::Soft || !description.is_empty());
+        rows.push(InterruptRow {
+            name,
+            kind,
+            description: description.join(" "),
+            counts,
+            per_cpu,
+        });
+    }
+
+    (cpu_names, rows)
+}
+
+/// Reads a interrupt file and parses its content.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_interrupts(path: &str, kind: InterruptKind) -> (Vec<String>, Vec<InterruptRow>) {
+    match File::open(path) {
+        Ok(file) => parse_interrupts(BufReader::new(file), kind),
+        Err(_) => (vec![], vec![]),
+    }
+}
+
+/// Reorders per CPU counters from one list of CPU names to another.
+///
+/// CPUs which are missing in the source list get a count of zero.
+///
+/// # Arguments
+///
+/// * 'counts' - counters by the index of 'from'
+/// * 'from' - CPU names of the counters
+/// * 'to' - CPU names of the result
+///
+/// # Panic
+///
+/// This function won't panic.
+fn align_counts(counts: &[u64], from: &[String], to: &[String]) -> Vec<u64> {
+    to.iter()
+        .map(|name| {
+            from.iter()
+                .position(|x| x == name)
+                .and_then(|i| counts.get(i))
+                .copied()
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Calculates the interrupt rates based on two different measurements.
+///
+/// Rows without previous measurement (e.g. newly registered interrupts) get a rate of zero.
+///
+/// # Arguments
+///
+/// * 'previous' - previous counters mapped by kind and name
+/// * 'current' - current rows
+/// * 'elapsed' - elapsed time between both measurements in seconds
+///
+/// # Panic
+///
+/// This function won't panic.
+fn calculate_rates(
+    previous: &HashMap<(InterruptKind, String), Vec<u64>>,
+    current: &[InterruptRow],
+    elapsed: f64,
+) -> Vec<InterruptInfo> {
+    current
+        .iter()
+        .map(|row| {
+            let rates: Vec<f64> = match previous.get(&(row.kind, row.name.clone())) {
+                Some(old) => row
+                    .counts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &c)| util::rate(old.get(i).copied().unwrap_or(c), c, elapsed))
+                    .collect(),
+                _ => vec![0.0; row.counts.len()],
+            };
+
+            InterruptInfo {
+                name: row.name.clone(),
+                kind: row.kind,
+                description: row.description.clone(),
+                total: row.counts.iter().sum(),
+                total_rate: rates.iter().sum(),
+                cpu_rates: match row.per_cpu {
+                    true => rates,
+                    false => vec![],
+                },
+            }
+        })
+        .collect()
+}
+
+/// Initializes a thread to collect and send the interrupt rates each second.
+///
+/// Reads /proc/interrupts and /proc/softirqs and calculates the rates of all interrupts.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_data_collection_thread() -> mpsc::Receiver<Sample<InterruptStats>> {
+    let (tx, rx) = mpsc::channel();
+    let dur = time::Duration::from_millis(1000);
+
+    let mut previous: HashMap<(InterruptKind, String), Vec<u64>> = HashMap::new();
+    let mut previous_names: Vec<String> = Vec::new();
+    let mut previous_time = Instant::now();
+
+    // Thread for the data collection
+    thread::spawn(move || loop {
+        let mut sample = Sample {
+            time: Instant::now(),
+            data: InterruptStats::default(),
+        };
+        let (cpu_names, mut rows) = read_interrupts(PROC_INTERRUPTS, InterruptKind::Hard);
+        let (soft_names, soft_rows) = read_interrupts(PROC_SOFTIRQS, InterruptKind::Soft);
+        rows.extend(soft_rows.into_iter().map(|mut row| {
+            if row.per_cpu {
+                row.counts = align_counts(&row.counts, &soft_names, &cpu_names);
+            }
+            row
+        }));
+
+        // the online CPUs changed, compare the counters of the same CPUs
+        if previous_names != cpu_names {
+            for row in rows.iter().filter(|r| r.per_cpu) {
+                if let Some(old) = previous.get_mut(&(row.kind, row.name.clone())) {
+                    *old = align_counts(old, &previous_names, &cpu_names);
+                }
+            }
+            previous_names = cpu_names.clone();
+        }
+
+        let elapsed = sample.elapsed_since(previous_time);
+        previous_time = sample.time;
+
+        sample.data.interrupts = calculate_rates(&previous, &rows, elapsed);
+        sample.data.cpu_names = cpu_names;
+
+        previous = rows
+            .into_iter()
+            .map(|row| ((row.kind, row.name), row.counts))
+            .collect();
+
+        let _ = tx.send(sample);
+
+        thread::sleep(dur);
+    });
+
+    rx
+}
+
+/// Number of columns in front of the per cpu columns
+const FIXED_COLUMNS: usize = 5;
+
+/// Stores the state data of the widget.
+pub struct InterruptsWidget {
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected item.
+    item_index: usize,
+    /// Index of column to sort by.
+    sort_index: usize,
+    /// Index of current selected column.
+    column_index: usize,
+    /// Sort the column descending or ascending.
+    sort_descending: bool,
+    /// Show softirqs in addition to hardware interrupts.
+    show_softirqs: bool,
+    /// Stores data inside the table.
+    stats: InterruptStats,
+    /// Used to receive data from the thread which reads interrupt information.
+    dc_thread: mpsc::Receiver<Sample<InterruptStats>>,
+}
+
+impl InterruptsWidget {
+    /// Creates new interrupts widget with default values.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new() -> Self {
+        let mut a = Self {
+            table_state: TableState::default(),
+            item_index: 0,
+            sort_index: 2,
+            column_index: 2,
+            sort_descending: true,
+            show_softirqs: true,
+            stats: Default::default(),
+            dc_thread: init_data_collection_thread(),
+        };
+        a.table_state.select(Some(0));
+        a
+    }
+
+    /// Returns the number of selectable columns.
+    fn column_count(&self) -> usize {
+        FIXED_COLUMNS + self.stats.cpu_names.len()
+    }
+
+    /// Sorts interrupt data ascending or descending by the current selected column.
+    fn sort(&mut self) {
+        let sort_index = self.sort_index;
+        let sort_descending = self.sort_descending;
+        self.stats.interrupts.sort_by(|a, b| {
+            let s = match sort_index {
+                0 => a.name.cmp(&b.name),
+                1 => a.kind.get_name().cmp(b.kind.get_name()),
+                2 => a
+                    .total_rate
+                    .partial_cmp(&b.total_rate)
+                    .unwrap_or(Ordering::Equal),
+                3 => a.total.cmp(&b.total),
+                4 => a.description.cmp(&b.description),
+                i => {
+                    let cpu = i - FIXED_COLUMNS;
+                    let a = a.cpu_rates.get(cpu).copied().unwrap_or_default();
+                    let b = b.cpu_rates.get(cpu).copied().unwrap_or_default();
+                    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                }
+            };
+            if sort_descending {
+                Ordering::reverse(s)
+            } else {
+                s
+            }
+        });
+    }
+
+    /// Retrieves information from the update thread and store the new data in the widget.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        // Recv data from the data collector thread
+        if let Ok(sample) = self.dc_thread.try_recv() {
+            let mut stats = sample.data;
+            if !self.show_softirqs {
+                stats.interrupts.retain(|i| i.kind == InterruptKind::Hard);
+            }
+            self.stats = stats;
+            self.sort();
+
+            // Keep the selection inside the table
+            if self.item_index >= self.stats.interrupts.len() {
+                self.item_index = self.stats.interrupts.len().saturating_sub(1);
+                self.table_state.select(Some(self.item_index));
+            }
+            if self.column_index >= self.column_count() {
+                self.column_index = self.column_count() - 1;
+            }
+        }
+    }
+
+    /// Draws the interrupt table and a per cpu summary in a given Rect.
+    ///
+    /// Only as many cpu columns as fit into the area are drawn. If a cpu column is selected,
+    /// the cpu columns are scrolled so the selected column stays visible.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+            .split(inner);
+
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let mut widths = vec![
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Length(28),
+        ];
+
+        // Determine which cpu columns are visible
+        let fixed_width: u16 = 10 + 5 + 9 + 12 + 28 + FIXED_COLUMNS as u16;
+        let cpu_count = self.stats.cpu_names.len();
+        let visible_cpus = ((inner.width.saturating_sub(fixed_width)) / 9) as usize;
+        let first_cpu = if self.column_index >= FIXED_COLUMNS + visible_cpus {
+            self.column_index + 1 - FIXED_COLUMNS - visible_cpus
+        } else {
+            0
+        };
+        let last_cpu = cpu_count.min(first_cpu + visible_cpus);
+        widths.extend((first_cpu..last_cpu).map(|_| Constraint::Length(8)));
+
+        // Create new header row in table
+        let mut header_names = vec!["IRQ", "Type", "Rate", "Total", "Device"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        header_names.extend(self.stats.cpu_names[first_cpu..last_cpu].iter().cloned());
+
+        let column_index = self.column_index;
+        let header_cells = header_names.into_iter().enumerate().map(|(i, h)| {
+            let i = if i >= FIXED_COLUMNS { i + first_cpu } else { i };
+            if i == column_index {
+                Cell::from(h).style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+            } else {
+                Cell::from(h)
+            }
+        });
+        let header = Row::new(header_cells).style(header_style).height(1);
+
+        // Populate rows of table
+        let rows = self.stats.interrupts.iter().map(|i| {
+            let mut cells = vec![
+                Cell::from(format!("{: >9}", i.name)),
+                Cell::from(i.kind.get_name()),
+                Cell::from(format!(
+                    "{: >9}",
+                    util::to_humanreadable_count(i.total_rate)
+                )),
+                Cell::from(format!("{: >12}", i.total)),
+                Cell::from(i.description.clone()),
+            ];
+            // rows which aren't counted per CPU leave the cpu columns empty
+            for cpu in first_cpu..last_cpu {
+                cells.push(match i.cpu_rates.get(cpu) {
+                    Some(rate) => {
+                        Cell::from(format!("{: >8}", util::to_humanreadable_count(*rate)))
+                    }
+                    None => Cell::from(""),
+                });
+            }
+            Row::new(cells).height(1)
+        });
+
+        // Create new table
+        let table = Table::new(rows)
+            .header(header)
+            .highlight_style(selected_style)
+            .widths(&widths);
+        f.render_stateful_widget(table, chunks[0], &mut self.table_state);
+
+        // Sum up the interrupt load per cpu
+        let mut spans = vec![Span::styled(
+            "Per CPU: ",
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for (cpu, name) in self.stats.cpu_names.iter().enumerate() {
+            let rate: f64 = self
+                .stats
+                .interrupts
+                .iter()
+                .map(|i| i.cpu_rates.get(cpu).copied().unwrap_or_default())
+                .sum();
+            spans.push(Span::raw(format!(
+                "{} {}/s  ",
+                name,
+                util::to_humanreadable_count(rate)
+            )));
+        }
+        f.render_widget(Paragraph::new(Spans::from(spans)), chunks[1]);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        match key {
+            // Navigate by row
+            Key::Down if self.item_index + 1 < self.stats.interrupts.len() => {
+                self.item_index += 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            Key::Up if self.item_index > 0 => {
+                self.item_index -= 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            // Navigate by column
+            Key::Right if self.column_index + 1 < self.column_count() => {
+                self.column_index += 1;
+            }
+            Key::Left if self.column_index > 0 => {
+                self.column_index -= 1;
+            }
+            // Show or hide softirqs
+            Key::Char('t') => {
+                self.show_softirqs = !self.show_softirqs;
+                if !self.show_softirqs {
+                    self.stats
+                        .interrupts
+                        .retain(|i| i.kind == InterruptKind::Hard);
+                }
+            }
+            // Sort by current selected column
+            Key::Char('s') => {
+                if self.sort_index == self.column_index {
+                    self.sort_descending = !self.sort_descending;
+                }
+
+                self.sort_index = self.column_index;
+                self.sort();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_counter_rows() {
+        let content = "           CPU0       CPU1
+  0:         36          0   IO-APIC   2-edge      timer
+LOC:    1234567    7654321   Local timer interrupts
+ERR:          3
+";
+        let (cpu_names, rows) = parse_interrupts(content.as_bytes(), InterruptKind::Hard);
+        assert_eq!(cpu_names, vec!["cpu0", "cpu1"]);
+        assert_eq!(rows.len(), 3);
+
+        assert_eq!(rows[0].counts, vec![36, 0]);
+        assert_eq!(rows[0].description, "IO-APIC 2-edge timer");
+        assert!(rows[0].per_cpu);
+        assert!(rows[1].per_cpu);
+
+        assert_eq!(rows[2].name, "ERR");
+        assert_eq!(rows[2].counts, vec![3]);
+        assert!(!rows[2].per_cpu);
+    }
+
+    #[test]
+    fn align_softirqs_by_cpu_name() {
+        // CPU1 is offline, /proc/softirqs still lists it
+        let interrupts = "           CPU0       CPU2
+LOC:         10         20   Local timer interrupts
+";
+        let softirqs = "                    CPU0       CPU1       CPU2
+      TIMER:          1          2          3
+";
+        let (cpu_names, _) = parse_interrupts(interrupts.as_bytes(), InterruptKind::Hard);
+        let (soft_names, rows) = parse_interrupts(softirqs.as_bytes(), InterruptKind::Soft);
+        assert!(rows[0].per_cpu);
+        assert_eq!(
+            align_counts(&rows[0].counts, &soft_names, &cpu_names),
+            vec![1, 3]
+        );
+
+        // a CPU which came online has no previous counter
+        assert_eq!(
+            align_counts(&[10, 20], &cpu_names, &soft_names),
+            vec![10, 0, 20]
+        );
+    }
+
+    #[test]
+    fn single_counter_rows_have_no_cpu_rates() {
+        let row = InterruptRow {
+            name: "ERR".to_string(),
+            kind: InterruptKind::Hard,
+            description: String::new(),
+            counts: vec![5],
+            per_cpu: false,
+        };
+        let mut previous = HashMap::new();
+        previous.insert((InterruptKind::Hard, "ERR".to_string()), vec![3]);
+
+        let info = calculate_rates(&previous, &[row], 2.0);
+        assert_eq!(info[0].total_rate, 1.0);
+        assert!(info[0].cpu_rates.is_empty());
+    }
+}
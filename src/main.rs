@@ -14,17 +14,41 @@ use tui::{
 mod util;
 use util::InputHandler;
 
+// Module for reading and storing user settings
+mod config;
+use config::Config;
+
 // Module for reading CPU usage data
 mod cpu;
-use cpu::CpuWidget;
+use cpu::{CpuViewMode, CpuWidget};
 
 // Module for reading memory usage data
 mod mem;
 use mem::MemoryWidget;
 
+// Module for reading NUMA node and process memory placement
+mod numa;
+
+// Module for reading zram devices and the zswap pool
+mod zram;
+
+// Module for reading transparent and explicit hugepage usage
+mod hugepages;
+
 // Module for reading disk usage data
 mod disk;
-use disk::DiskWidget;
+use disk::{DiskWidget, MountFilter};
+
+// Module for reading disk I/O statistics
+mod diskio;
+use diskio::DiskIoWidget;
+
+// Module for scanning directory sizes
+mod dirscan;
+
+// Module for reading the block device tree
+mod blockdev;
+use blockdev::BlockTreeWidget;
 
 // Module for managing processes
 mod processes;
@@ -34,6 +58,18 @@ use processes::ProcessesWidget;
 mod network;
 use network::NetworkWidget;
 
+// Module for reading sockets and their processes
+mod connections;
+use connections::ConnectionsWidget;
+
+// Module for reading interrupt statistics
+mod interrupts;
+use interrupts::InterruptsWidget;
+
+// Module for reading kernel slab caches
+mod slab;
+use slab::SlabWidget;
+
 /// Defines the different application states.
 #[derive(PartialEq)]
 enum AppState {
@@ -51,17 +87,28 @@ struct AppLogic {
     current_widget: WidgetType,
     /// defines whether selected widget is highlighted
     show_selected_widget: bool,
+    /// widget which is shown in the large area at the bottom
+    bottom_widget: WidgetType,
 }
 
 /// Defines the supported widget types. A widget enables an user to to view specific system information like memory
 /// usage, processes or network usage.
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 enum WidgetType {
-    CPU,
+    Cpu,
     Network,
     Disk,
     Processes,
     Memory,
+    Interrupts,
+    MemoryDetails,
+    OomEvents,
+    Slab,
+    DiskIo,
+    BlockDevices,
+    NetworkInterfaces,
+    Connections,
+    ListeningPorts,
 }
 
 impl WidgetType {
@@ -71,35 +118,103 @@ impl WidgetType {
             WidgetType::Memory => (0, "Memory"),
             WidgetType::Disk => (1, "Partitions"),
             WidgetType::Network => (2, "Network"),
-            WidgetType::CPU => (3, "CPU"),
+            WidgetType::Cpu => (3, "CPU"),
             WidgetType::Processes => (4, "Processes"),
+            WidgetType::Interrupts => (4, "Interrupts"),
+            WidgetType::MemoryDetails => (4, "Memory Details"),
+            WidgetType::OomEvents => (4, "OOM Events"),
+            WidgetType::Slab => (4, "Slab Caches"),
+            WidgetType::DiskIo => (4, "Disk I/O"),
+            WidgetType::BlockDevices => (4, "Block Devices"),
+            WidgetType::NetworkInterfaces => (4, "Network Interfaces"),
+            WidgetType::Connections => (4, "Connections"),
+            WidgetType::ListeningPorts => (4, "Listening Ports"),
         }
     }
 
     /// Returns a widget type by the associated id
-    fn get_by_id(id: usize) -> WidgetType {
+    ///
+    /// Since multiple widgets share the large area at the bottom, the currently shown
+    /// bottom widget is returned for its id.
+    fn get_by_id(id: usize, bottom_widget: WidgetType) -> WidgetType {
         match id {
             0 => WidgetType::Memory,
             1 => WidgetType::Disk,
             2 => WidgetType::Network,
-            3 => WidgetType::CPU,
-            4 => WidgetType::Processes,
+            3 => WidgetType::Cpu,
+            4 => bottom_widget,
             _ => WidgetType::Memory, //default case
         }
     }
 
+    /// Returns the next widget which can be shown in the large area at the bottom
+    fn get_next_bottom_widget(&self) -> WidgetType {
+        match *self {
+            WidgetType::Processes => WidgetType::Interrupts,
+            WidgetType::Interrupts => WidgetType::MemoryDetails,
+            WidgetType::MemoryDetails => WidgetType::OomEvents,
+            WidgetType::OomEvents => WidgetType::Slab,
+            WidgetType::Slab => WidgetType::DiskIo,
+            WidgetType::DiskIo => WidgetType::BlockDevices,
+            WidgetType::BlockDevices => WidgetType::NetworkInterfaces,
+            WidgetType::NetworkInterfaces => WidgetType::Connections,
+            WidgetType::Connections => WidgetType::ListeningPorts,
+            _ => WidgetType::Processes,
+        }
+    }
+
     /// Returns the help text of a widget
     fn get_help_text(&self) -> &str {
         match *self {
-            WidgetType::Memory => "",
-            WidgetType::Disk => ", up: previous, down: next",
-            WidgetType::Network => "",
-            WidgetType::CPU => ", SPACE: show/hide all cores",
+            WidgetType::Memory => ", SPACE: show/hide history",
+            WidgetType::Disk => ", s:sort, left/right: move header, up/down: select partition, i: inodes/sizes, a: show/hide pseudo filesystems, ENTER: explore",
+            WidgetType::Network => ", up/down: select interface, v: show/hide virtual interfaces",
+            WidgetType::Cpu => ", SPACE: show/hide all cores",
             WidgetType::Processes => {
-                ", s:sort, left/right:  move header, up/down: select process, n: niceness"
+                ", s:sort, left/right:  move header, up/down: select process, n: niceness, o: oom_score_adj, m: memory columns, c: connections, ENTER: details"
             }
+            WidgetType::Interrupts => {
+                ", s:sort, left/right: move header, up/down: select irq, t: show/hide softirqs"
+            }
+            WidgetType::MemoryDetails => "",
+            WidgetType::OomEvents => "",
+            WidgetType::Slab => ", s:sort, left/right: move header, up/down: select cache",
+            WidgetType::DiskIo => ", up/down: select device, p: show/hide partitions",
+            WidgetType::BlockDevices => ", up/down: select device",
+            WidgetType::NetworkInterfaces => {
+                ", up/down: select interface, v: show/hide virtual interfaces"
+            }
+            WidgetType::Connections => {
+                ", s:sort, left/right: move header, up/down: select socket, f: filter, r: reset filter, u: show/hide unix sockets"
+            }
+            WidgetType::ListeningPorts => ", up/down: select port",
+        }
+    }
+}
+
+impl AppLogic {
+    /// Returns a widget type by the associated id, see [WidgetType::get_by_id]
+    fn get_widget_by_id(&self, id: usize) -> WidgetType {
+        WidgetType::get_by_id(id, self.bottom_widget)
+    }
+
+    /// Shows the next widget in the large area at the bottom.
+    ///
+    /// The selection follows the new widget if the old one was selected.
+    fn switch_bottom_widget(&mut self) {
+        let selected = self.current_widget == self.bottom_widget;
+        self.bottom_widget = self.bottom_widget.get_next_bottom_widget();
+        if selected {
+            self.current_widget = self.bottom_widget;
         }
     }
+
+    /// Shows and selects the given widget in the large area at the bottom.
+    fn show_bottom_widget(&mut self, widget: WidgetType) {
+        self.bottom_widget = widget;
+        self.current_widget = widget;
+        self.show_selected_widget = true;
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -109,17 +224,34 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // Initialize the different widgets
-    let mut disk_widget = DiskWidget::new();
-    let mut cpu_widget = CpuWidget::new();
+    let mut config = Config::load();
+    let mut disk_widget = DiskWidget::new(
+        MountFilter::new(
+            config.get("disk_include").unwrap_or(""),
+            config.get("disk_exclude").unwrap_or(""),
+        ),
+        config
+            .get("disk_full_alert")
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(disk::DEFAULT_FULL_ALERT_HOURS),
+    );
+    let mut cpu_widget =
+        CpuWidget::new(CpuViewMode::get_by_name(config.get("cpu_view").unwrap_or("")));
     let mut mem_widget = MemoryWidget::new();
     let mut processes_widget = ProcessesWidget::new();
     let mut network_widget = NetworkWidget::new();
+    let mut interrupts_widget = InterruptsWidget::new();
+    let mut slab_widget = SlabWidget::new();
+    let mut disk_io_widget = DiskIoWidget::new();
+    let mut block_tree_widget = BlockTreeWidget::new();
+    let mut connections_widget = ConnectionsWidget::new();
 
     // Initialize app state
     let mut app = AppLogic {
         state: AppState::Interaction,
         current_widget: WidgetType::Processes,
         show_selected_widget: false,
+        bottom_widget: WidgetType::Processes,
     };
 
     // Initialize input handler
@@ -128,13 +260,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Define sleep duration for thread
     const SLEEP_DURATION: Duration = Duration::from_millis(100);
 
-    // Define all used widgets
-    let data_widgets = vec![
+    // Define all used widgets, the bottom widget is added dynamically
+    let data_widgets = [
         WidgetType::Memory,
         WidgetType::Disk,
         WidgetType::Network,
-        WidgetType::CPU,
-        WidgetType::Processes,
+        WidgetType::Cpu,
     ];
 
     // Clear terminal - otherwise the screen may contain old data
@@ -147,6 +278,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         processes_widget.update();
         disk_widget.update();
         network_widget.update();
+        interrupts_widget.update();
+        slab_widget.update();
+        disk_io_widget.update();
+        block_tree_widget.update();
+        connections_widget.set_active(matches!(
+            app.bottom_widget,
+            WidgetType::Connections | WidgetType::ListeningPorts
+        ));
+        connections_widget.update();
 
         // Draw the tui
         terminal.draw(|f| {
@@ -178,7 +318,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .split(chunks[0]);
 
             // Draw data widgets
-            for dw in &data_widgets {
+            for dw in data_widgets.iter().chain([app.bottom_widget].iter()) {
                 let (id, name) = dw.get_value();
 
                 // Determine whether the widget is selected
@@ -198,12 +338,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         mem_widget.draw(f, boxes[0], create_block(name, selected, navigation));
                     }
                     WidgetType::Disk => {
-                        disk_widget.draw(f, boxes[1], create_block(name, selected, navigation));
+                        // filesystems which will be full soon are counted in the title
+                        let title = match disk_widget.get_alert_count() {
+                            0 => name.to_string(),
+                            n => format!("{} ({} filling up)", name, n),
+                        };
+                        disk_widget.draw(f, boxes[1], create_block(&title, selected, navigation));
                     }
                     WidgetType::Network => {
-                        network_widget.draw(f, boxes[2], create_block(name, selected, navigation));
+                        // the compact view shows a single interface
+                        let title = match network_widget.get_selected_interface() {
+                            Some(interface) => format!("{} {}", name, interface),
+                            None => name.to_string(),
+                        };
+                        network_widget.draw(f, boxes[2], create_block(&title, selected, navigation));
                     }
-                    WidgetType::CPU => {
+                    WidgetType::Cpu => {
                         cpu_widget.draw(f, chunks[1], create_block(name, selected, navigation));
                     }
                     WidgetType::Processes => {
@@ -213,21 +363,103 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             create_block(name, selected, navigation),
                         );
                     }
+                    WidgetType::Interrupts => {
+                        interrupts_widget.draw(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::MemoryDetails => {
+                        mem_widget.draw_details(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::OomEvents => {
+                        processes_widget.draw_oom_events(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::Slab => {
+                        slab_widget.draw(f, chunks[2], create_block(name, selected, navigation));
+                    }
+                    WidgetType::DiskIo => {
+                        disk_io_widget.draw(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::BlockDevices => {
+                        block_tree_widget.draw(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::NetworkInterfaces => {
+                        network_widget.draw_interfaces(
+                            f,
+                            chunks[2],
+                            create_block(name, selected, navigation),
+                        );
+                    }
+                    WidgetType::Connections => {
+                        let title = match connections_widget.get_filter() {
+                            Some(filter) => format!("{} ({})", name, filter),
+                            None => name.to_string(),
+                        };
+                        connections_widget.draw(
+                            f,
+                            chunks[2],
+                            create_block(&title, selected, navigation),
+                        );
+                    }
+                    WidgetType::ListeningPorts => {
+                        // Ports reachable from the network are highlighted in the table
+                        let title = match connections_widget.get_exposed_count() {
+                            0 => name.to_string(),
+                            n => format!("{} ({} on all interfaces)", name, n),
+                        };
+                        connections_widget.draw_listening(
+                            f,
+                            chunks[2],
+                            create_block(&title, selected, navigation),
+                        );
+                    }
                 }
             }
 
+            // The directory explorer of a filesystem is shown over the bottom area
+            disk_widget.draw_explorer(f, chunks[2]);
+
             // Generate help text which is displayed to user
             let mut help_text =
                 "ESC: navigation/interaction, CTRL-v:show/hide selected widget".to_string();
 
+            if app.state == AppState::Navigation {
+                help_text += ", TAB: switch view";
+            }
+
             if app.show_selected_widget && app.state == AppState::Interaction {
-                // Append help text of current selected widget
-                help_text += app.current_widget.get_help_text();
+                // Append help text of current selected widget, the directory explorer of the
+                // disk widget has its own keys
+                if app.current_widget == WidgetType::Disk && disk_widget.is_exploring() {
+                    help_text += disk_widget.get_help_text();
+                } else {
+                    help_text += app.current_widget.get_help_text();
+                }
 
                 // The help text needs to be dynamically appended since the processes widget provides multiple
                 // features depending on the internal state of the widget.
                 if app.current_widget == WidgetType::Processes {
                     help_text += processes_widget.get_help_text();
+                } else if app.current_widget == WidgetType::Cpu {
+                    help_text += &cpu_widget.get_help_text();
                 }
             }
 
@@ -241,9 +473,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Get new keyboard events 
         let event = input_handler.next();
 
-        if event.is_ok() {
-            let input = event.unwrap();
-
+        if let Ok(input) = event {
             // Depending on the app state different key bindings are used
             match app.state {
                 AppState::Interaction => {
@@ -252,19 +482,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         match app.current_widget {
                             WidgetType::Processes => {
                                 processes_widget.handle_input(input);
+                                if let Some(pid) = processes_widget.take_connections_request() {
+                                    connections_widget.show_process(pid);
+                                    app.show_bottom_widget(WidgetType::Connections);
+                                }
                             }
-                            WidgetType::CPU => {
+                            WidgetType::Cpu => {
                                 cpu_widget.handle_input(input);
                             }
                             WidgetType::Disk => {
                                 disk_widget.handle_input(input);
                             }
-                            WidgetType::Network => {
+                            WidgetType::Network | WidgetType::NetworkInterfaces => {
                                 network_widget.handle_input(input);
                             }
                             WidgetType::Memory => {
                                 mem_widget.handle_input(input);
                             }
+                            // The details view has no keys, SPACE only toggles the chart
+                            // of the memory widget above
+                            WidgetType::MemoryDetails => {}
+                            WidgetType::Interrupts => {
+                                interrupts_widget.handle_input(input);
+                            }
+                            WidgetType::OomEvents => {}
+                            WidgetType::Slab => {
+                                slab_widget.handle_input(input);
+                            }
+                            WidgetType::DiskIo => {
+                                disk_io_widget.handle_input(input);
+                            }
+                            WidgetType::BlockDevices => {
+                                block_tree_widget.handle_input(input);
+                            }
+                            WidgetType::Connections => {
+                                connections_widget.handle_input(input);
+                            }
+                            WidgetType::ListeningPorts => {
+                                connections_widget.handle_listening_input(input);
+                            }
                         }
                     }
                     
@@ -290,32 +546,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         // Navigation
                         Key::Right => {
                             let (id, _) = app.current_widget.get_value();
-                            if id < data_widgets.len() - 1 {
-                                app.current_widget = WidgetType::get_by_id(id + 1);
+                            if id < data_widgets.len() {
+                                app.current_widget = app.get_widget_by_id(id + 1);
                             }
                         }
                         Key::Left => {
                             let (id, _) = app.current_widget.get_value();
                             if id > 0 {
-                                app.current_widget = WidgetType::get_by_id(id - 1);
+                                app.current_widget = app.get_widget_by_id(id - 1);
                             }
                         }
                         Key::Up => {
                             let (id, _) = app.current_widget.get_value();
                             if id == 3 {
-                                app.current_widget = WidgetType::get_by_id(1);
+                                app.current_widget = app.get_widget_by_id(1);
                             } else if id == 4 {
-                                app.current_widget = WidgetType::get_by_id(3);
+                                app.current_widget = app.get_widget_by_id(3);
                             }
                         }
                         Key::Down => {
                             let (id, _) = app.current_widget.get_value();
                             if id < 3 {
-                                app.current_widget = WidgetType::get_by_id(3);
+                                app.current_widget = app.get_widget_by_id(3);
                             } else if id == 3 {
-                                app.current_widget = WidgetType::get_by_id(4);
+                                app.current_widget = app.get_widget_by_id(4);
                             }
                         }
+                        // Switch the widget in the large area at the bottom
+                        Key::Char('\t') => {
+                            app.switch_bottom_widget();
+                        }
                         // Switch between app states
                         Key::Esc => {
                             app.state = AppState::Interaction;
@@ -335,12 +595,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Sleep
         thread::sleep(SLEEP_DURATION);
     }
+
+    // Remember the settings for the next run, a failure is not worth bothering the user
+    config.set("cpu_view", cpu_widget.get_view_mode().get_name());
+    let _ = config.save();
+
     Ok(())
 }
 
 /// Creates a new empty block which can be populated by a widget.
 /// The border style is dynamically modified based on the selection and navigation state.
-fn create_block(name: &str, selected: bool, navigation: bool) -> Block {
+fn create_block(name: &str, selected: bool, navigation: bool) -> Block<'_> {
     let mut color = Color::Cyan;
 
     if !navigation && selected {
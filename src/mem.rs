@@ -1,71 +1,210 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::sync::mpsc;
 use std::thread;
-use std::time;
+use std::time::{self, Instant};
 use std::{io::BufRead, io::BufReader};
 use termion::event::Key;
 use tui::{
     backend::Backend,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     terminal::Frame,
-    widgets::{Block, Gauge},
+    text::Span,
+    widgets::{Axis, Block, Cell, Chart, Dataset, Gauge, GraphType, Row, Table},
 };
 
-#[derive(Default, Debug)]
+use crate::hugepages::{self, HugePagePool};
+use crate::numa::{self, NumaNode};
+use crate::util::{self, Sample};
+use crate::zram::{self, ZramDevice, ZswapStats};
+
+/// Paging activity per second, calculated from /proc/vmstat
+#[derive(Default, Debug, Clone, Copy)]
+pub struct PagingRates {
+    /// Pages swapped in per second (pswpin)
+    pub swap_in: f64,
+    /// Pages swapped out per second (pswpout)
+    pub swap_out: f64,
+    /// Major page faults per second (pgmajfault)
+    pub major_faults: f64,
+}
+
+/// Memory information read from /proc/meminfo
+///
+/// All sizes are in KiB.
+#[derive(Default, Debug, Clone)]
 pub struct MemInfo {
-    pub mem_total: u32,
-    pub mem_free: u32,
-    pub mem_available: u32,
-    pub swap_total: u32,
-    pub swap_free: u32,
-    pub swap_cached: u32,
+    pub mem_total: u64,
+    pub mem_free: u64,
+    pub mem_available: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+    pub swap_cached: u64,
+    /// All fields of /proc/meminfo by name. Sizes are in KiB, HugePages_* are page counts.
+    pub fields: HashMap<String, u64>,
+    /// Paging activity since the previous measurement
+    pub paging: PagingRates,
+    /// Memory usage of the NUMA nodes
+    pub numa_nodes: Vec<NumaNode>,
+    /// Initialized zram devices
+    pub zram_devices: Vec<ZramDevice>,
+    /// zswap state, None if the kernel has no zswap support
+    pub zswap: Option<ZswapStats>,
+    /// Explicit hugepage pools by page size
+    pub hugepage_pools: Vec<HugePagePool>,
+    /// Transparent hugepage mode, empty if not supported
+    pub thp_mode: String,
 }
-/// Get the current ram usage
-/// 
-/// This function reads the current meminfo from "/proc/meminfo" and returns a Result.
-/// The Result is either a MemInfo-objet or an Error.
-/// 
-/// See https://man7.org/linux/man-pages/man5/proc.5.html for morte information.
-/// 
+
+impl MemInfo {
+    /// Returns the value of a /proc/meminfo field or 0 if the kernel doesn't provide it.
+    pub fn get(&self, name: &str) -> u64 {
+        self.fields.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Parses the content of /proc/meminfo into a map of field names and values.
+///
+/// Each line has the format "Name:   value [kB]". Lines which can't be parsed are skipped,
+/// so new or reordered fields of future kernels don't affect other fields.
+///
+/// # Arguments
+///
+/// * 'reader' - content of the file
+///
 /// # Panic
-/// 
+///
 /// This function won't panic.
-pub fn show_ram_usage() -> Result<MemInfo, Box<dyn std::error::Error>> {
-    let meminfo = "/proc/meminfo";
+pub fn parse_meminfo<R: BufRead>(reader: R) -> HashMap<String, u64> {
+    let mut fields = HashMap::new();
 
-    let mut mem_info: MemInfo = Default::default();
+    for line in reader.lines() {
+        let row = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
 
-    let file = File::open(meminfo)?;
-    let reader = BufReader::new(file);
-    let mut mem_numbers: [u32; 6] = [0; 6];
-    let mut count = 0;
+        let (name, value) = match row.split_once(':') {
+            Some(x) => x,
+            None => continue,
+        };
+        let value = match value.split_whitespace().next().map(|v| v.parse::<u64>()) {
+            Some(Ok(x)) => x,
+            _ => continue,
+        };
+        fields.insert(name.trim().to_string(), value);
+    }
+
+    fields
+}
+
+/// Parses the content of /proc/vmstat into a map of counter names and values.
+///
+/// Each line has the format "name value".
+///
+/// # Arguments
+///
+/// * 'reader' - content of the file
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_vmstat<R: BufRead>(reader: R) -> HashMap<String, u64> {
+    let mut counters = HashMap::new();
 
     for line in reader.lines() {
         let row = match line {
             Ok(x) => x,
             Err(_) => break,
         };
-        if row.starts_with("Mem") || row.starts_with("Swap") {
-            let mut row_values = row.split_whitespace();
-
-            row_values.next();
-            match row_values.next() {
-                // handling default-values in MemoryWidget as error
-                Some(x) => mem_numbers[count] = x.parse().unwrap_or_default(),
-                None => break,
-            }
 
-            count += 1;
+        let mut columns = row.split_whitespace();
+        if let (Some(name), Some(Ok(value))) = (columns.next(), columns.next().map(|v| v.parse())) {
+            counters.insert(name.to_string(), value);
         }
     }
 
-    mem_info.mem_total = mem_numbers[0];
-    mem_info.mem_free = mem_numbers[1];
-    mem_info.mem_available = mem_numbers[2];
-    mem_info.swap_cached = mem_numbers[3];
-    mem_info.swap_total = mem_numbers[4];
-    mem_info.swap_free = mem_numbers[5];
+    counters
+}
+
+/// Reads the counters of /proc/vmstat.
+///
+/// Returns an empty map if the file could not be read.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_vmstat() -> HashMap<String, u64> {
+    match File::open("/proc/vmstat") {
+        Ok(file) => parse_vmstat(BufReader::new(file)),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Calculates the paging activity per second based on two /proc/vmstat measurements.
+///
+/// # Arguments
+///
+/// * 'previous' - previous counters
+/// * 'current' - current counters
+/// * 'elapsed' - elapsed time between both measurements in seconds
+///
+/// # Panic
+///
+/// This function won't panic.
+fn calculate_paging_rates(
+    previous: &HashMap<String, u64>,
+    current: &HashMap<String, u64>,
+    elapsed: f64,
+) -> PagingRates {
+    if previous.is_empty() || elapsed <= 0.0 {
+        return Default::default();
+    }
+
+    let rate = |name: &str| {
+        let old = previous.get(name).copied().unwrap_or_default();
+        let new = current.get(name).copied().unwrap_or_default();
+        util::rate(old, new, elapsed)
+    };
+
+    PagingRates {
+        swap_in: rate("pswpin"),
+        swap_out: rate("pswpout"),
+        major_faults: rate("pgmajfault"),
+    }
+}
+
+/// Get the current ram usage
+/// 
+/// This function reads the current meminfo from "/proc/meminfo" and returns a Result.
+/// The Result is either a MemInfo-objet or an Error.
+/// 
+/// See https://man7.org/linux/man-pages/man5/proc.5.html for morte information.
+/// 
+/// # Panic
+/// 
+/// This function won't panic.
+pub fn show_ram_usage() -> Result<MemInfo, Box<dyn std::error::Error>> {
+    let file = File::open("/proc/meminfo")?;
+    let fields = parse_meminfo(BufReader::new(file));
+
+    let mut mem_info = MemInfo {
+        fields,
+        ..Default::default()
+    };
+    mem_info.mem_total = mem_info.get("MemTotal");
+    mem_info.mem_free = mem_info.get("MemFree");
+    mem_info.mem_available = mem_info.get("MemAvailable");
+    mem_info.swap_cached = mem_info.get("SwapCached");
+    mem_info.swap_total = mem_info.get("SwapTotal");
+    mem_info.swap_free = mem_info.get("SwapFree");
+
+    mem_info.zram_devices = zram::read_zram_devices();
+    mem_info.zswap = zram::read_zswap(&mem_info.fields);
+    mem_info.hugepage_pools = hugepages::read_hugepage_pools();
+    mem_info.thp_mode = hugepages::read_thp_mode();
 
     Ok(mem_info)
 }
@@ -77,18 +216,33 @@ pub fn show_ram_usage() -> Result<MemInfo, Box<dyn std::error::Error>> {
 /// # Panic
 /// 
 /// This function won't panic.
-pub fn init_data_collection_thread() -> mpsc::Receiver<MemInfo> {
+pub fn init_data_collection_thread() -> mpsc::Receiver<Sample<MemInfo>> {
     let (tx, rx) = mpsc::channel();
     let dur = time::Duration::from_millis(500);
 
+    let mut previous_vmstat = HashMap::new();
+    let mut previous_numa = Vec::new();
+    let mut previous_time = Instant::now();
+
     // Thread for the data collection
     thread::spawn(move || loop {
-        let m = match show_ram_usage() {
-            Ok(a) => a,
-            Err(_) => Default::default(),
+        let mut sample = Sample {
+            time: Instant::now(),
+            data: show_ram_usage().unwrap_or_default(),
         };
+        let elapsed = sample.elapsed_since(previous_time);
+        previous_time = sample.time;
+
+        let vmstat = read_vmstat();
+        sample.data.paging = calculate_paging_rates(&previous_vmstat, &vmstat, elapsed);
+        previous_vmstat = vmstat;
 
-        let _ = tx.send(m);
+        let numa_counters = numa::read_node_counters();
+        sample.data.numa_nodes =
+            numa::calculate_node_rates(&previous_numa, &numa_counters, elapsed);
+        previous_numa = numa_counters;
+
+        let _ = tx.send(sample);
 
         thread::sleep(dur);
     });
@@ -105,7 +259,7 @@ const SIZES: [&str; 4] = [" KiB", " MiB", " GiB", " TiB"];
 /// # Panic
 /// 
 /// This funciton won't panic.
-pub fn calc_ram_to_fit_size(mem_size: u32) -> String {
+pub fn calc_ram_to_fit_size(mem_size: u64) -> String {
     let mut count = 0;
 
     if mem_size == 0 {
@@ -115,7 +269,7 @@ pub fn calc_ram_to_fit_size(mem_size: u32) -> String {
     let mut size = mem_size as f64;
 
     while size > 1000.0 {
-        size = size / 1024.0;
+        size /= 1024.0;
         count += 1;
     }
 
@@ -124,9 +278,52 @@ pub fn calc_ram_to_fit_size(mem_size: u32) -> String {
     size_string + SIZES[count]
 }
 
+/// Number of measurements kept in the history
+const HISTORY_LENGTH: usize = 300;
+
+/// Memory usage of a single measurement in percent, used for the history chart
+#[derive(Default, Debug, Clone, Copy)]
+pub struct MemoryUsage {
+    /// Used memory (total - available) in percent of the total memory
+    pub used: f64,
+    /// Buffers and page cache in percent of the total memory
+    pub cached: f64,
+    /// Used swap in percent of the total swap
+    pub swap: f64,
+}
+
+impl MemoryUsage {
+    /// Calculates the memory usage in percent of a measurement.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn from_mem_info(m: &MemInfo) -> Self {
+        let percent = |value: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                value as f64 / total as f64 * 100.0
+            }
+        };
+
+        MemoryUsage {
+            used: percent(m.mem_total.saturating_sub(m.mem_available), m.mem_total),
+            cached: percent(m.get("Buffers") + m.get("Cached"), m.mem_total),
+            swap: percent(m.swap_total.saturating_sub(m.swap_free), m.swap_total),
+        }
+    }
+}
+
 pub struct MemoryWidget {
     mem_info: MemInfo,
-    dc_thread: mpsc::Receiver<MemInfo>,
+    // Paging activity of the previous measurements
+    paging_history: Vec<PagingRates>,
+    // Memory usage of the previous measurements
+    usage_history: Vec<MemoryUsage>,
+    // Show the usage history instead of the gauges
+    show_chart: bool,
+    dc_thread: mpsc::Receiver<Sample<MemInfo>>,
 }
 
 impl MemoryWidget {
@@ -138,6 +335,9 @@ impl MemoryWidget {
     pub fn new() -> Self {
         Self {
             mem_info: Default::default(),
+            paging_history: Vec::new(),
+            usage_history: Vec::new(),
+            show_chart: false,
             dc_thread: init_data_collection_thread(),
         }
     }
@@ -149,17 +349,33 @@ impl MemoryWidget {
     pub fn update(&mut self) {
         // Recv data from the data collector thread
 
-        let mem_info = self.dc_thread.try_recv();
+        // All measurements since the last update are added to the histories
+        while let Ok(sample) = self.dc_thread.try_recv() {
+            let mem_info = sample.data;
+            // If a certain threshold is reached, remove an entry from the beginning
+            // -> keeps the vec at a fixed size
+            if self.paging_history.len() == HISTORY_LENGTH {
+                self.paging_history.remove(0);
+            }
+            self.paging_history.push(mem_info.paging);
+
+            // Measurements without data are errors, which shouldn't show up as drops
+            if mem_info.mem_total > 0 {
+                if self.usage_history.len() == HISTORY_LENGTH {
+                    self.usage_history.remove(0);
+                }
+                self.usage_history
+                    .push(MemoryUsage::from_mem_info(&mem_info));
+            }
 
-        // check before so unwrap is safe
-        if mem_info.is_ok() {
-            self.mem_info = mem_info.unwrap();
+            self.mem_info = mem_info;
         }
     }
 
     /// Draws memory information in a given Rect.
     /// 
     /// Checks whether a swap-memory exists or not.
+    /// On multi-node systems the used and free memory of each NUMA node is shown as well.
     /// 
     /// # Arguments
     /// 
@@ -176,6 +392,11 @@ impl MemoryWidget {
     /// This function draws the MemoryWidget based on its mem_info.
     /// Call the update function before to get current information.
     pub fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        if self.show_chart {
+            self.draw_usage_chart(f, rect, block);
+            return;
+        }
+
         let block_chunks = Layout::default()
             .constraints([Constraint::Length(2), Constraint::Length(2)])
             .margin(1)
@@ -185,7 +406,7 @@ impl MemoryWidget {
         f.render_widget(block, rect);
 
         // check for no memory, return cause of error
-        // may add error-message to display 
+        // may add error-message to display
         if self.mem_info.mem_total == 0 {
             return;
         }
@@ -194,10 +415,23 @@ impl MemoryWidget {
         let mem_usage = ((self.mem_info.mem_total - self.mem_info.mem_available) as f64)
             / (self.mem_info.mem_total as f64);
         let label_mem = format!("{:.2}%", mem_usage * 100.0);
-        let title_mem = "Memory: ".to_string()
+        let mut title_mem = "Memory: ".to_string()
             + &calc_ram_to_fit_size(self.mem_info.mem_total - self.mem_info.mem_available)
             + " of "
             + &calc_ram_to_fit_size(self.mem_info.mem_total);
+        // the used memory per node shows imbalances on multi-node systems, the details
+        // view has the complete NUMA table
+        if self.mem_info.numa_nodes.len() > 1 {
+            for node in &self.mem_info.numa_nodes {
+                let used = node.mem_total.saturating_sub(node.mem_free);
+                title_mem += &format!(
+                    ", node{} {:.0}% ({} free)",
+                    node.id,
+                    used as f64 / node.mem_total.max(1) as f64 * 100.0,
+                    calc_ram_to_fit_size(node.mem_free)
+                );
+            }
+        }
         let gauge_mem = Gauge::default()
             .block(Block::default().title(title_mem))
             .gauge_style(
@@ -212,16 +446,27 @@ impl MemoryWidget {
 
         //check whether swap exists, otherwise return
         if self.mem_info.swap_total == 0 {
-            return
+            return;
         }
 
-        // calc infos for swap-memory
-        let mem_swap = self.mem_info.swap_cached as f64 / self.mem_info.swap_total as f64;
+        // calc infos for swap-memory, swap cache is part of the used swap
+        let swap_used = self
+            .mem_info
+            .swap_total
+            .saturating_sub(self.mem_info.swap_free);
+        let mem_swap = swap_used as f64 / self.mem_info.swap_total as f64;
         let label_swap = format!("{:.2}%", mem_swap * 100.0);
-        let title_swap = "Swap: ".to_string()
-            + &calc_ram_to_fit_size(self.mem_info.swap_total - self.mem_info.swap_free)
+        let mut title_swap = "Swap: ".to_string()
+            + &calc_ram_to_fit_size(swap_used)
             + " of "
             + &calc_ram_to_fit_size(self.mem_info.swap_total);
+        // zram swap lives in memory, show how well it is compressed
+        let (orig, compressed) = self.mem_info.zram_devices.iter().fold((0, 0), |(o, c), d| {
+            (o + d.orig_data_size, c + d.compr_data_size)
+        });
+        if compressed > 0 {
+            title_swap += &format!(" (zram {:.1}x)", orig as f64 / compressed as f64);
+        }
         let gauge_swap = Gauge::default()
             .block(Block::default().title(title_swap))
             .gauge_style(
@@ -235,6 +480,543 @@ impl MemoryWidget {
         f.render_widget(gauge_swap, block_chunks[1]);
     }
 
-    // Currently unused
-    pub fn handle_input(&mut self, _key: Key) {}
+    /// Draws a detailed breakdown of /proc/meminfo in a given Rect.
+    ///
+    /// Shows a table with the most important memory categories, a gauge comparing
+    /// the committed memory with the commit limit and a chart of the paging activity.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw_details<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+            .split(inner);
+        // The NUMA table is only shown on multi-node systems
+        let numa_height = match self.mem_info.numa_nodes.len() {
+            0 | 1 => 0,
+            n => n as u16 + 2,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(1),
+                    Constraint::Length(numa_height),
+                    Constraint::Length(2),
+                ]
+                .as_ref(),
+            )
+            .split(columns[0]);
+
+        if numa_height > 0 {
+            self.draw_numa_table(f, chunks[1]);
+        }
+
+        // Compressed memory is only shown if zram or zswap is available
+        let compression_rows = self.mem_info.zram_devices.len() + self.mem_info.zswap.iter().len();
+        let compression_height = match compression_rows {
+            0 => 0,
+            n => n as u16 + 2,
+        };
+        let hugepage_rows = self.mem_info.hugepage_pools.len()
+            + if self.mem_info.thp_mode.is_empty() {
+                0
+            } else {
+                3
+            };
+        let hugepage_height = match hugepage_rows {
+            0 => 0,
+            n => n as u16 + 2,
+        };
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(3),
+                    Constraint::Length(compression_height),
+                    Constraint::Length(hugepage_height),
+                ]
+                .as_ref(),
+            )
+            .split(columns[1]);
+
+        self.draw_paging_chart(f, right_chunks[0]);
+        if compression_height > 0 {
+            self.draw_compression_table(f, right_chunks[1]);
+        }
+        if hugepage_height > 0 {
+            self.draw_hugepage_table(f, right_chunks[2]);
+        }
+
+        let m = &self.mem_info;
+
+        // (name, size in KiB, description)
+        let rows: Vec<(&str, u64, &str)> = vec![
+            ("Total", m.mem_total, "usable physical memory"),
+            (
+                "Used",
+                m.mem_total.saturating_sub(m.mem_available),
+                "total - available",
+            ),
+            (
+                "Available",
+                m.mem_available,
+                "estimate for starting new applications",
+            ),
+            ("Free", m.mem_free, "completely unused memory"),
+            ("Buffers", m.get("Buffers"), "raw block device buffers"),
+            (
+                "Page cache",
+                m.get("Cached"),
+                "file contents cached in memory",
+            ),
+            ("Shmem", m.get("Shmem"), "shared memory and tmpfs"),
+            ("Slab", m.get("Slab"), "kernel data structure caches"),
+            (
+                "  Reclaimable",
+                m.get("SReclaimable"),
+                "slab that can be freed",
+            ),
+            (
+                "  Unreclaimable",
+                m.get("SUnreclaim"),
+                "slab that can't be freed",
+            ),
+            (
+                "Dirty",
+                m.get("Dirty"),
+                "waiting to be written back to disk",
+            ),
+            (
+                "Writeback",
+                m.get("Writeback"),
+                "actively written back to disk",
+            ),
+            (
+                "Anon",
+                m.get("AnonPages"),
+                "anonymous pages mapped into processes",
+            ),
+            ("Mapped", m.get("Mapped"), "files mapped into processes"),
+            (
+                "Committed",
+                m.get("Committed_AS"),
+                "memory allocated by processes",
+            ),
+            (
+                "Commit limit",
+                m.get("CommitLimit"),
+                "limit for strict overcommit",
+            ),
+        ];
+
+        let header = Row::new(vec!["Name", "Size", "Share", "Description"])
+            .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+            .height(1);
+
+        let table_rows = rows.iter().map(|(name, size, description)| {
+            let share = if m.mem_total > 0 {
+                format!("{:.1}%", *size as f64 / m.mem_total as f64 * 100.0)
+            } else {
+                String::new()
+            };
+            Row::new(vec![
+                Cell::from(*name),
+                Cell::from(format!("{: >10}", calc_ram_to_fit_size(*size))),
+                Cell::from(format!("{: >6}", share)),
+                Cell::from(*description),
+            ])
+        });
+
+        let table = Table::new(table_rows).header(header).widths(&[
+            Constraint::Length(16),
+            Constraint::Length(11),
+            Constraint::Length(7),
+            Constraint::Min(1),
+        ]);
+        f.render_widget(table, chunks[0]);
+
+        // Committed memory compared to the commit limit
+        let committed = m.get("Committed_AS");
+        let limit = m.get("CommitLimit");
+        if limit == 0 {
+            return;
+        }
+        let ratio = committed as f64 / limit as f64;
+        let title = "Committed: ".to_string()
+            + &calc_ram_to_fit_size(committed)
+            + " of "
+            + &calc_ram_to_fit_size(limit);
+        let gauge = Gauge::default()
+            .block(Block::default().title(title))
+            .gauge_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .bg(Color::Black)
+                    .add_modifier(Modifier::ITALIC | Modifier::BOLD),
+            )
+            .label(format!("{:.2}%", ratio * 100.0))
+            // overcommit may exceed the limit
+            .ratio(ratio.min(1.0));
+        f.render_widget(gauge, chunks[2]);
+    }
+
+    /// Draws the memory usage and allocation rates per NUMA node.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_numa_table<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let header = Row::new(vec!["Node", "Used", "Free", "Total", "Hit/s", "Miss/s"])
+            .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+            .height(1);
+
+        let rows = self.mem_info.numa_nodes.iter().map(|n| {
+            Row::new(vec![
+                Cell::from(format!("node{}", n.id)),
+                Cell::from(format!(
+                    "{: >10}",
+                    calc_ram_to_fit_size(n.mem_total.saturating_sub(n.mem_free))
+                )),
+                Cell::from(format!("{: >10}", calc_ram_to_fit_size(n.mem_free))),
+                Cell::from(format!("{: >10}", calc_ram_to_fit_size(n.mem_total))),
+                Cell::from(format!("{: >7}", util::to_humanreadable_count(n.hit_rate))),
+                // misses are allocations on a node other than intended
+                Cell::from(format!("{: >7}", util::to_humanreadable_count(n.miss_rate))).style(
+                    match n.miss_rate > 0.0 {
+                        true => Style::default().fg(Color::Yellow),
+                        false => Style::default(),
+                    },
+                ),
+            ])
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title("NUMA"))
+            .widths(&[
+                Constraint::Length(7),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ]);
+        f.render_widget(table, rect);
+    }
+
+    /// Draws the original and compressed size of the zram devices and the zswap pool.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_compression_table<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let header = Row::new(vec![
+            "Device", "Algo", "Size", "Original", "Compr.", "Mem used", "Ratio",
+        ])
+        .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+        .height(1);
+
+        // (name, algorithm, sizes in KiB: device size, original, compressed, used, ratio)
+        // zram reports bytes, zswap KiB
+        let mut devices: Vec<(String, &str, [u64; 4], f64)> = self
+            .mem_info
+            .zram_devices
+            .iter()
+            .map(|d| {
+                (
+                    d.name.clone(),
+                    d.algorithm.as_str(),
+                    [
+                        d.disk_size / 1024,
+                        d.orig_data_size / 1024,
+                        d.compr_data_size / 1024,
+                        d.mem_used_total / 1024,
+                    ],
+                    d.compression_ratio(),
+                )
+            })
+            .collect();
+        if let Some(z) = &self.mem_info.zswap {
+            let name = match z.enabled {
+                true => "zswap".to_string(),
+                false => "zswap off".to_string(),
+            };
+            // the size of the zswap pool is limited relative to the total memory
+            let limit = self.mem_info.mem_total * z.max_pool_percent / 100;
+            devices.push((
+                name,
+                z.compressor.as_str(),
+                [limit, z.orig_data_size, z.pool_size, z.pool_size],
+                z.compression_ratio(),
+            ));
+        }
+
+        let rows = devices.into_iter().map(|(name, algorithm, sizes, ratio)| {
+            let mut cells = vec![Cell::from(name), Cell::from(algorithm.to_string())];
+            cells.extend(
+                sizes
+                    .iter()
+                    .map(|s| Cell::from(format!("{: >10}", calc_ram_to_fit_size(*s)))),
+            );
+            cells.push(Cell::from(format!("{: >5.1}x", ratio)));
+            Row::new(cells)
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title("Compressed memory"))
+            .widths(&[
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(7),
+            ]);
+        f.render_widget(table, rect);
+    }
+
+    /// Draws the usage of transparent hugepages and of the explicit hugepage pools.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_hugepage_table<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let m = &self.mem_info;
+        let header = Row::new(vec!["Type", "Used", "Total", "Free", "Rsvd", "Surplus"])
+            .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+            .height(1);
+
+        let size = |kib: u64| Cell::from(format!("{: >10}", calc_ram_to_fit_size(kib)));
+        let mut rows: Vec<Row> = Vec::new();
+
+        // Transparent hugepages have no pool, only their usage is known
+        if !m.thp_mode.is_empty() {
+            for (name, field) in [
+                ("THP anon", "AnonHugePages"),
+                ("THP shmem", "ShmemHugePages"),
+                ("THP file", "FileHugePages"),
+            ] {
+                rows.push(Row::new(vec![Cell::from(name), size(m.get(field))]));
+            }
+        }
+
+        for pool in &m.hugepage_pools {
+            rows.push(Row::new(vec![
+                Cell::from(calc_ram_to_fit_size(pool.page_size)),
+                size(pool.used_size()),
+                size(pool.total * pool.page_size),
+                size(pool.free * pool.page_size),
+                Cell::from(format!("{: >7}", pool.reserved)),
+                Cell::from(format!("{: >7}", pool.surplus)),
+            ]));
+        }
+
+        let title = match m.thp_mode.is_empty() {
+            true => "Hugepages".to_string(),
+            false => format!("Hugepages (THP: {})", m.thp_mode),
+        };
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title(title))
+            .widths(&[
+                Constraint::Length(9),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ]);
+        f.render_widget(table, rect);
+    }
+
+    /// Draws the history of swap-ins, swap-outs and major page faults per second.
+    ///
+    /// The y-axis is scaled to the highest value in the history.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_paging_chart<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let to_points = |value: fn(&PagingRates) -> f64| {
+            self.paging_history
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i as f64, value(p)))
+                .collect::<Vec<_>>()
+        };
+        let swap_in = to_points(|p| p.swap_in);
+        let swap_out = to_points(|p| p.swap_out);
+        let major_faults = to_points(|p| p.major_faults);
+
+        let max = swap_in
+            .iter()
+            .chain(swap_out.iter())
+            .chain(major_faults.iter())
+            .map(|(_, y)| *y)
+            .fold(1.0, f64::max);
+
+        let current = self.mem_info.paging;
+        let datasets = vec![
+            Dataset::default()
+                .name(format!(
+                    "swap in {} pages/s",
+                    util::to_humanreadable_count(current.swap_in)
+                ))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Green))
+                .graph_type(GraphType::Line)
+                .data(&swap_in),
+            Dataset::default()
+                .name(format!(
+                    "swap out {} pages/s",
+                    util::to_humanreadable_count(current.swap_out)
+                ))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Red))
+                .graph_type(GraphType::Line)
+                .data(&swap_out),
+            Dataset::default()
+                .name(format!(
+                    "major faults {}/s",
+                    util::to_humanreadable_count(current.major_faults)
+                ))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Yellow))
+                .graph_type(GraphType::Line)
+                .data(&major_faults),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().title("Paging (per second)"))
+            .x_axis(Axis::default().bounds([0.0, HISTORY_LENGTH as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(vec![
+                        Span::styled("0", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            util::to_humanreadable_count(max),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                    ])
+                    .bounds([0.0, max]),
+            )
+            .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+        f.render_widget(chart, rect);
+    }
+
+    /// Draws the history of the used memory, the page cache and the used swap.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_usage_chart<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let to_points = |value: fn(&MemoryUsage) -> f64| {
+            self.usage_history
+                .iter()
+                .enumerate()
+                .map(|(i, u)| (i as f64, value(u)))
+                .collect::<Vec<_>>()
+        };
+        let used = to_points(|u| u.used);
+        let cached = to_points(|u| u.cached);
+        let swap = to_points(|u| u.swap);
+
+        let mut datasets = vec![
+            Dataset::default()
+                .name("used")
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Cyan))
+                .graph_type(GraphType::Line)
+                .data(&used),
+            Dataset::default()
+                .name("cached")
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Yellow))
+                .graph_type(GraphType::Line)
+                .data(&cached),
+        ];
+        if self.mem_info.swap_total > 0 {
+            datasets.push(
+                Dataset::default()
+                    .name("swap")
+                    .marker(symbols::Marker::Braille)
+                    .style(Style::default().fg(Color::Red))
+                    .graph_type(GraphType::Line)
+                    .data(&swap),
+            );
+        }
+
+        let chart = Chart::new(datasets)
+            .block(block)
+            .x_axis(Axis::default().bounds([0.0, HISTORY_LENGTH as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(vec![
+                        Span::styled("  0", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("100", Style::default().add_modifier(Modifier::BOLD)),
+                    ])
+                    .bounds([0.0, 100.0]),
+            );
+        f.render_widget(chart, rect);
+    }
+
+    /// Handles the input for the widget.
+    ///
+    /// The space bar toggles between the gauges and the usage history chart.
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        // Show or hide the usage history
+        if let Key::Char(' ') = key {
+            self.show_chart = !self.show_chart;
+        }
+    }
 }
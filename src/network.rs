@@ -1,22 +1,42 @@
-use std::fs::File;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fs::{self, File};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::Path;
 use std::sync::mpsc;
 use std::thread;
-use std::time;
+use std::time::{self, Instant};
 use std::{io::BufRead, io::BufReader};
 use termion::event::Key;
 use tui::{
     backend::Backend,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
     terminal::Frame,
-    text::Spans,
-    widgets::{Block, Paragraph, Wrap},
+    text::{Span, Spans},
+    widgets::{
+        Axis, Block, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, TableState, Wrap,
+    },
 };
 
-use crate::util;
+use crate::util::{self, Sample};
 
 const PROC_NET_DEV: &str = "/proc/net/dev";
+const SYS_CLASS_NET: &str = "/sys/class/net";
+const PROC_NET_IF_INET6: &str = "/proc/net/if_inet6";
+
+/// Number of samples in the throughput history, 2.5 minutes
+const HISTORY_LENGTH: usize = 300;
+
+/// Number of samples after which the interface details are read again, 5 seconds
+const DETAILS_INTERVAL: usize = 10;
+
+/// Hardware type of loopback interfaces in /sys/class/net/<if>/type
+const ARPHRD_LOOPBACK: &str = "772";
+
 // all information which are used or can be used later
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct NetworkInfo {
     pub interface: String,
     pub rec_bytes: usize,
@@ -27,79 +47,283 @@ pub struct NetworkInfo {
     pub send_packets: usize,
     pub send_errs: usize,
     pub send_drop: usize,
+    /// Loopback interface like "lo"
+    pub is_loopback: bool,
+    /// Software interface without a device, e.g. veth, bridges or docker0
+    pub is_virtual: bool,
+    pub details: InterfaceDetails,
+}
+
+/// State and addresses of an interface, read from /sys/class/net/<if>/
+#[derive(Default, Debug, Clone)]
+pub struct InterfaceDetails {
+    /// Operational state, e.g. "up", "down" or "unknown"
+    pub operstate: String,
+    /// Whether a link is detected, None if the interface is down
+    pub carrier: Option<bool>,
+    /// Link speed in Mbit/s, None if unknown (e.g. virtual interfaces)
+    pub speed: Option<u64>,
+    /// "full" or "half", None if unknown
+    pub duplex: Option<String>,
+    pub mtu: usize,
+    pub mac: String,
+    /// IPv4 addresses with prefix length, e.g. "192.168.0.2/24"
+    pub ipv4: Vec<String>,
+    /// IPv6 addresses with prefix length, e.g. "fe80::1/64"
+    pub ipv6: Vec<String>,
 }
 
-/// Get the current network I/O
+impl NetworkInfo {
+    /// Returns the utilization of the link in percent, based on the busier direction.
+    ///
+    /// # Arguments
+    ///
+    /// * 'rates' - received and sent bytes per second
+    ///
+    /// Returns None if the link speed is unknown.
+    pub fn get_utilization(&self, rates: (f64, f64)) -> Option<f64> {
+        let speed = self.details.speed? as f64 * 1_000_000.0;
+        Some(rates.0.max(rates.1) * 8.0 / speed * 100.0)
+    }
+}
+
+/// Reads the state, link speed, MTU and MAC address of an interface from sysfs.
 ///
-/// This function reads the current network information from "/proc/net/dev" and returns a Result.
-/// The Result is either a NetworkInfo-objet or an Error.
+/// # Panic
 ///
-/// See https://www.kernel.org/doc/html/latest/networking/statistics.html for more information.
+/// This function won't panic.
+fn read_interface_details(interface: &str) -> InterfaceDetails {
+    let path = Path::new(SYS_CLASS_NET).join(interface);
+    // some attributes can't be read while the interface is down
+    let read = |name: &str| {
+        fs::read_to_string(path.join(name))
+            .map(|x| x.trim().to_string())
+            .ok()
+    };
+
+    InterfaceDetails {
+        operstate: read("operstate").unwrap_or_default(),
+        carrier: read("carrier").map(|x| x == "1"),
+        speed: read("speed")
+            .and_then(|x| x.parse::<i64>().ok())
+            .filter(|x| *x > 0)
+            .map(|x| x as u64),
+        duplex: read("duplex").filter(|x| x != "unknown"),
+        mtu: read("mtu").and_then(|x| x.parse().ok()).unwrap_or_default(),
+        mac: read("address").unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// Parses the content of /proc/net/if_inet6 into the IPv6 addresses of each interface.
+///
+/// Each line contains the address as 32 hex digits, the interface index, the prefix
+/// length, scope, flags and the interface name.
 ///
 /// # Panic
 ///
 /// This function won't panic.
-pub fn get_network_io() -> Result<NetworkInfo, Box<dyn std::error::Error>> {
-    let file = File::open(PROC_NET_DEV)?;
-    let reader = BufReader::new(file);
-    let mut network_info: NetworkInfo = Default::default();
+pub fn parse_if_inet6(content: &str) -> HashMap<String, Vec<String>> {
+    let mut addresses: HashMap<String, Vec<String>> = HashMap::new();
 
-    // read network io info with iterator
-    let mut line_iterator = reader.lines();
+    for line in content.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        if columns.len() < 6 {
+            continue;
+        }
+        let address = match u128::from_str_radix(columns[0], 16) {
+            Ok(x) => Ipv6Addr::from(x),
+            Err(_) => continue,
+        };
+        let prefix = u8::from_str_radix(columns[2], 16).unwrap_or_default();
+        addresses
+            .entry(columns[5].to_string())
+            .or_default()
+            .push(format!("{}/{}", address, prefix));
+    }
 
-    // skipping the first two lines containing a description
-    line_iterator.next();
-    line_iterator.next();
-    // filter / skip local network activity
-    line_iterator.next();
+    addresses
+}
+
+/// Queries the IPv4 addresses of all interfaces.
+///
+/// getifaddrs retrieves the addresses from the kernel via netlink.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_ipv4_addresses() -> HashMap<String, Vec<String>> {
+    let mut addresses: HashMap<String, Vec<String>> = HashMap::new();
+
+    let mut list: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: getifaddrs allocates the list, which is released by freeifaddrs below
+    if unsafe { libc::getifaddrs(&mut list) } != 0 {
+        return addresses;
+    }
+
+    let mut current = list;
+    while !current.is_null() {
+        // SAFETY: the entries of the list stay valid until freeifaddrs is called, the
+        // addresses of the AF_INET family are sockaddr_in structs
+        let entry = unsafe { &*current };
+        current = entry.ifa_next;
+        if entry.ifa_addr.is_null()
+            || i32::from(unsafe { (*entry.ifa_addr).sa_family }) != libc::AF_INET
+        {
+            continue;
+        }
+
+        let to_ip = |addr: *const libc::sockaddr| {
+            let addr = unsafe { &*(addr as *const libc::sockaddr_in) };
+            Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr))
+        };
+        let prefix = match entry.ifa_netmask.is_null() {
+            true => 32,
+            false => u32::from(to_ip(entry.ifa_netmask)).count_ones(),
+        };
+        let name = unsafe { CStr::from_ptr(entry.ifa_name) }
+            .to_string_lossy()
+            .to_string();
+        addresses
+            .entry(name)
+            .or_default()
+            .push(format!("{}/{}", to_ip(entry.ifa_addr), prefix));
+    }
 
-    for line in line_iterator {
+    unsafe { libc::freeifaddrs(list) };
+    addresses
+}
+
+/// Parses the content of /proc/net/dev.
+///
+/// The first two lines contain a description, the other lines the counters of one
+/// interface each. The interface name is separated by a colon, which may be directly
+/// followed by the first counter.
+///
+/// See https://www.kernel.org/doc/html/latest/networking/statistics.html for more information.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_net_dev<R: BufRead>(reader: R) -> Vec<NetworkInfo> {
+    let mut interfaces = Vec::new();
+
+    // skipping the first two lines containing a description
+    for line in reader.lines().skip(2) {
         let row = match line {
             Ok(x) => x,
             _ => break,
         };
 
-        // collect iterator into vector
-        let row_values = row.split_whitespace().collect::<Vec<_>>();
-
-        // check for the network adapter with the most incoming trafic -> row_values[1] is the value for total bytes recieved
-        // unwrap_or_default, because the default (0) will always be skipped
-        if row_values[1].parse::<usize>().unwrap_or_default() > network_info.rec_bytes {
-            // unwrap_or_default to match "normal" thread error, where a Default::default will be returned -> DiskWidget handels defaults
-            network_info.interface = row_values[0].to_string();
-            network_info.rec_bytes = row_values[1].parse().unwrap_or_default();
-            network_info.rec_packets = row_values[2].parse().unwrap_or_default();
-            network_info.rec_errs = row_values[3].parse().unwrap_or_default();
-            network_info.rec_drop = row_values[4].parse().unwrap_or_default();
-            network_info.send_bytes = row_values[9].parse().unwrap_or_default();
-            network_info.send_packets = row_values[10].parse().unwrap_or_default();
-            network_info.send_errs = row_values[11].parse().unwrap_or_default();
-            network_info.send_drop = row_values[12].parse().unwrap_or_default();
+        let (interface, counters) = match row.split_once(':') {
+            Some(x) => x,
+            None => continue,
+        };
+        let row_values: Vec<usize> = counters
+            .split_whitespace()
+            .map(|x| x.parse().unwrap_or_default())
+            .collect();
+        if row_values.len() < 12 {
+            continue;
+        }
+
+        interfaces.push(NetworkInfo {
+            interface: interface.trim().to_string(),
+            rec_bytes: row_values[0],
+            rec_packets: row_values[1],
+            rec_errs: row_values[2],
+            rec_drop: row_values[3],
+            send_bytes: row_values[8],
+            send_packets: row_values[9],
+            send_errs: row_values[10],
+            send_drop: row_values[11],
+            ..Default::default()
+        });
+    }
+
+    interfaces
+}
+
+/// Get the current network I/O of all interfaces
+///
+/// This function reads the current network information from "/proc/net/dev" and returns a Result.
+/// The Result is either a NetworkInfo-objet for each interface or an Error.
+///
+/// Interfaces are marked as virtual if their sysfs entry isn't backed by a device.
+/// The details contain the link state from sysfs and the IP addresses. They change
+/// rarely, so they are taken from the previous measurement unless a refresh is requested
+/// or a new interface appeared.
+///
+/// # Arguments
+///
+/// * `previous` - the interfaces of the previous measurement
+/// * `refresh_details` - whether the details of all interfaces are read again
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn get_network_io(
+    previous: &[NetworkInfo],
+    refresh_details: bool,
+) -> Result<Vec<NetworkInfo>, Box<dyn std::error::Error>> {
+    let file = File::open(PROC_NET_DEV)?;
+    let mut interfaces = parse_net_dev(BufReader::new(file));
+
+    let known = |info: &NetworkInfo| previous.iter().find(|p| p.interface == info.interface);
+    if !refresh_details && interfaces.iter().all(|i| known(i).is_some()) {
+        for info in &mut interfaces {
+            if let Some(last) = known(info) {
+                info.is_loopback = last.is_loopback;
+                info.is_virtual = last.is_virtual;
+                info.details = last.details.clone();
+            }
         }
+        return Ok(interfaces);
     }
 
-    Ok(network_info)
+    let mut ipv4 = read_ipv4_addresses();
+    let mut ipv6 = parse_if_inet6(&fs::read_to_string(PROC_NET_IF_INET6).unwrap_or_default());
+
+    for info in &mut interfaces {
+        let path = Path::new(SYS_CLASS_NET).join(&info.interface);
+        info.is_loopback = fs::read_to_string(path.join("type"))
+            .map(|t| t.trim() == ARPHRD_LOOPBACK)
+            .unwrap_or(info.interface == "lo");
+        info.is_virtual = !path.join("device").exists();
+
+        info.details = read_interface_details(&info.interface);
+        info.details.ipv4 = ipv4.remove(&info.interface).unwrap_or_default();
+        info.details.ipv6 = ipv6.remove(&info.interface).unwrap_or_default();
+    }
+
+    Ok(interfaces)
 }
 
 /// Initializes a thread to collect and send the network information each 0.5 seconds.
 ///
-/// It will send a NetworkInfo-object with default values if an error occurs in get_network_io.
+/// It will send an empty list if an error occurs in get_network_io. Each sample carries
+/// the time the counters were read at. The interface details are refreshed every
+/// DETAILS_INTERVAL samples.
 ///
 /// # Panic
 ///
 /// This function won't panic.
-pub fn init_data_collection_thread() -> mpsc::Receiver<NetworkInfo> {
+pub fn init_data_collection_thread() -> mpsc::Receiver<Sample<Vec<NetworkInfo>>> {
     let (tx, rx) = mpsc::channel();
     let dur = time::Duration::from_millis(500);
 
+    let mut previous: Vec<NetworkInfo> = Vec::new();
+    let mut count: usize = 0;
+
     // Thread for the data collection
     thread::spawn(move || loop {
-        let m = match get_network_io() {
-            Ok(a) => a,
-            Err(_) => Default::default(),
-        };
+        let time = Instant::now();
+        let refresh_details = count.is_multiple_of(DETAILS_INTERVAL);
+        let m = get_network_io(&previous, refresh_details).unwrap_or_default();
+        previous = m.clone();
+        count = count.wrapping_add(1);
 
-        let _ = tx.send(m);
+        let _ = tx.send(Sample { time, data: m });
 
         thread::sleep(dur);
     });
@@ -108,9 +332,21 @@ pub fn init_data_collection_thread() -> mpsc::Receiver<NetworkInfo> {
 }
 
 pub struct NetworkWidget {
-    current_info: NetworkInfo,
-    last_info: NetworkInfo,
-    dc_thread: mpsc::Receiver<NetworkInfo>,
+    /// The shown interfaces of the current sample
+    interfaces: Vec<NetworkInfo>,
+    /// All interfaces of the previous sample by name
+    last_info: HashMap<String, NetworkInfo>,
+    /// Time of the previous sample
+    last_time: Option<Instant>,
+    /// Received and sent bytes per second of all interfaces, the newest value is the last one
+    history: HashMap<String, Vec<(f64, f64)>>,
+    dc_thread: mpsc::Receiver<Sample<Vec<NetworkInfo>>>,
+    /// Show loopback and virtual interfaces
+    show_virtual: bool,
+    /// Name of the selected interface, the busiest interface is selected if not set
+    selected: Option<String>,
+    /// Used for the selection of the current row.
+    table_state: TableState,
 }
 
 impl NetworkWidget {
@@ -121,31 +357,96 @@ impl NetworkWidget {
     /// This function won't panic.
     pub fn new() -> Self {
         Self {
-            current_info: Default::default(),
-            last_info: Default::default(),
+            interfaces: vec![],
+            last_info: HashMap::new(),
+            last_time: None,
+            history: HashMap::new(),
             dc_thread: init_data_collection_thread(),
+            show_virtual: false,
+            selected: None,
+            table_state: TableState::default(),
         }
     }
     /// Updates the current information and rotates the older one
     ///
+    /// Loopback and virtual interfaces are hidden unless they are enabled or no other
+    /// interface exists, e.g. inside a container. The throughput history is kept for all
+    /// interfaces, also for hidden ones.
+    ///
+    /// All samples received since the last update are added to the history.
+    ///
     /// # Panic
     ///
     /// This function won't panic.
     pub fn update(&mut self) {
         // Recv data from the data collector thread
-        let network_info = self.dc_thread.try_recv();
-
-        if network_info.is_ok() {
-            self.last_info = NetworkInfo {
-                interface: self.current_info.interface.clone(),
-                ..self.current_info
+        while let Ok(sample) = self.dc_thread.try_recv() {
+            let elapsed = match self.last_time {
+                Some(x) => sample.elapsed_since(x),
+                None => 0.0,
             };
+            self.last_time = Some(sample.time);
+            let mut network_info = sample.data;
+
+            for info in &network_info {
+                let rates = match self.last_info.get(&info.interface) {
+                    Some(last) => (
+                        util::rate(last.rec_bytes as u64, info.rec_bytes as u64, elapsed),
+                        util::rate(last.send_bytes as u64, info.send_bytes as u64, elapsed),
+                    ),
+                    None => (0.0, 0.0),
+                };
+                let history = self.history.entry(info.interface.clone()).or_default();
+                history.push(rates);
+                if history.len() > HISTORY_LENGTH {
+                    history.remove(0);
+                }
+            }
+            // Forget removed interfaces
+            self.history
+                .retain(|name, _| network_info.iter().any(|i| &i.interface == name));
+            self.last_info = network_info
+                .iter()
+                .map(|i| (i.interface.clone(), i.clone()))
+                .collect();
+
+            let physical = network_info.iter().any(|i| !i.is_virtual);
+            let loopback_only = network_info.iter().all(|i| i.is_loopback);
+            if !self.show_virtual && !loopback_only {
+                network_info.retain(|i| !i.is_loopback && (!i.is_virtual || !physical));
+            }
+            self.interfaces = network_info;
+        }
+    }
 
-            // we network_info is ok / safe at this point
-            self.current_info = network_info.unwrap();
+    /// Returns the index of the selected interface.
+    fn get_selected_index(&self) -> Option<usize> {
+        match &self.selected {
+            Some(name) => self.interfaces.iter().position(|i| &i.interface == name),
+            None => None,
         }
+        .or_else(|| {
+            // the interface with the most incoming traffic
+            (0..self.interfaces.len()).max_by_key(|&i| self.interfaces[i].rec_bytes)
+        })
     }
-    /// Draws all network information in a given Rect.
+
+    /// Returns the name of the interface shown in the compact view.
+    pub fn get_selected_interface(&self) -> Option<&str> {
+        self.get_selected_index()
+            .map(|i| self.interfaces[i].interface.as_str())
+    }
+
+    /// Returns the current received and sent bytes per second of an interface.
+    fn get_rates(&self, info: &NetworkInfo) -> (f64, f64) {
+        self.history
+            .get(&info.interface)
+            .and_then(|h| h.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Draws the network information of the selected interface in a given Rect.
     ///
     /// # Arguments
     ///
@@ -159,46 +460,334 @@ impl NetworkWidget {
     ///
     /// # Usage
     ///
-    /// This function draws the NetworkInfo based on current_info and last_info.
+    /// This function draws the NetworkInfo based on current and last sample.
     /// Call the update function before to get current information.
-    ///
-    /// Call the update and draw function each 0.5seconds to get precise meassurements.
     pub fn draw<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
-        if self.last_info.rec_bytes > self.current_info.rec_bytes {
-            return;
-        }
+        let info = match self.get_selected_index() {
+            Some(i) => &self.interfaces[i],
+            None => {
+                f.render_widget(block, rect);
+                return;
+            }
+        };
 
-        // the factor is based on the refreshing-rate of the ui (500ms)
-        let receiving =
-            util::to_humanreadable((self.current_info.rec_bytes - self.last_info.rec_bytes) * 2)
-                + "/s";
-        let sending =
-            util::to_humanreadable((self.current_info.send_bytes - self.last_info.send_bytes) * 2)
-                + "/s";
+        let (rx, tx) = self.get_rates(info);
+        let receiving = util::to_humanreadable(rx as usize) + "/s";
+        let sending = util::to_humanreadable(tx as usize) + "/s";
+        let utilization = info.get_utilization((rx, tx)).map(|u| {
+            Span::styled(
+                format!("{:.1}%", u),
+                Style::default().fg(util::get_color_by_utilization(u)),
+            )
+        });
 
-        let text: Vec<tui::text::Spans>;
         // adjust information to size, showing less informations on smaller screens
-        if rect.width > 25 {
-            let total_received = util::to_humanreadable(self.current_info.rec_bytes);
-            let total_sent = util::to_humanreadable(self.current_info.send_bytes);
-            text = vec![
+        let mut text: Vec<tui::text::Spans> = if rect.width > 25 {
+            let total_received = util::to_humanreadable(info.rec_bytes);
+            let total_sent = util::to_humanreadable(info.send_bytes);
+            vec![
                 Spans::from(format!("Receiving      {}", receiving)),
                 Spans::from(format!("Total Received {}", total_received)),
                 Spans::from(format!("Sending        {}", sending)),
                 Spans::from(format!("Total Sent     {}", total_sent)),
-            ];
+            ]
         } else {
-            text = vec![
+            vec![
                 Spans::from("Receiving"),
-                Spans::from(format!("{}", receiving)),
+                Spans::from(receiving),
                 Spans::from("Sending"),
-                Spans::from(format!("{}", sending)),
-            ];
+                Spans::from(sending),
+            ]
+        };
+        // the utilization is only known for links with a speed
+        if let Some(utilization) = utilization {
+            let label = match rect.width > 25 {
+                true => "Utilization    ",
+                false => "Util ",
+            };
+            text.push(Spans::from(vec![Span::raw(label), utilization]));
         }
 
         let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
         f.render_widget(paragraph, rect);
     }
-    // Currently unused
-    pub fn handle_input(&mut self, _key: Key) {}
+
+    /// Draws a table of all shown interfaces with their rates and counters, the details
+    /// and the throughput history of the selected interface.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw_interfaces<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(inner);
+
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        let header = Row::new(vec![
+            "Interface",
+            "Rx/s",
+            "Tx/s",
+            "Util",
+            "Rx total",
+            "Tx total",
+            "Rx pkts",
+            "Tx pkts",
+            "Errors rx/tx",
+            "Drops rx/tx",
+        ])
+        .style(header_style)
+        .height(1);
+
+        // Populate rows of table
+        let rows = self.interfaces.iter().map(|i| {
+            let (rx, tx) = self.get_rates(i);
+            let bytes = |value: usize| format!("{: >10}", util::to_humanreadable(value));
+            let error_style = match i.rec_errs + i.send_errs {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Red),
+            };
+            let drop_style = match i.rec_drop + i.send_drop {
+                0 => Style::default(),
+                _ => Style::default().fg(Color::Yellow),
+            };
+            let utilization = match i.get_utilization((rx, tx)) {
+                Some(u) => Cell::from(format!("{: >5.1}%", u))
+                    .style(Style::default().fg(util::get_color_by_utilization(u))),
+                None => Cell::from(format!("{: >6}", "-")),
+            };
+            Row::new(vec![
+                Cell::from(i.interface.clone()),
+                Cell::from(format!("{}/s", bytes(rx as usize))),
+                Cell::from(format!("{}/s", bytes(tx as usize))),
+                utilization,
+                Cell::from(bytes(i.rec_bytes)),
+                Cell::from(bytes(i.send_bytes)),
+                Cell::from(format!("{: >10}", i.rec_packets)),
+                Cell::from(format!("{: >10}", i.send_packets)),
+                Cell::from(format!("{: >5}/{}", i.rec_errs, i.send_errs)).style(error_style),
+                Cell::from(format!("{: >5}/{}", i.rec_drop, i.send_drop)).style(drop_style),
+            ])
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(16),
+                Constraint::Length(12),
+                Constraint::Length(12),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(12),
+            ]);
+        self.table_state.select(self.get_selected_index());
+        f.render_stateful_widget(table, chunks[0], &mut self.table_state);
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(44), Constraint::Min(10)].as_ref())
+            .split(chunks[1]);
+        self.draw_details(f, bottom[0]);
+        self.draw_chart(f, bottom[1]);
+    }
+
+    /// Draws the link state and addresses of the selected interface.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_details<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let info = match self.get_selected_index() {
+            Some(i) => &self.interfaces[i],
+            None => return,
+        };
+        let details = &info.details;
+
+        let state_style = match details.operstate.as_str() {
+            "up" => Style::default().fg(Color::Green),
+            "down" | "lowerlayerdown" => Style::default().fg(Color::Red),
+            _ => Style::default(),
+        };
+        let carrier = match details.carrier {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "-",
+        };
+        let speed = match details.speed {
+            Some(s) if s >= 1000 && s % 1000 == 0 => format!("{} Gbit/s", s / 1000),
+            Some(s) => format!("{} Mbit/s", s),
+            None => String::from("unknown"),
+        };
+        let speed = match &details.duplex {
+            Some(d) => format!("{}, {} duplex", speed, d),
+            None => speed,
+        };
+        let utilization = match info.get_utilization(self.get_rates(info)) {
+            Some(u) => Span::styled(
+                format!("{:.1}%", u),
+                Style::default().fg(util::get_color_by_utilization(u)),
+            ),
+            None => Span::raw("-"),
+        };
+
+        let line = |name: &str, value: Span<'static>| {
+            Spans::from(vec![
+                Span::styled(
+                    format!("{: <9}", name),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                value,
+            ])
+        };
+        let mut text = vec![
+            line(
+                "State",
+                Span::styled(details.operstate.clone(), state_style),
+            ),
+            line("Carrier", Span::raw(carrier)),
+            line("Speed", Span::raw(speed)),
+            line("Util", utilization),
+            line("MTU", Span::raw(details.mtu.to_string())),
+            line("MAC", Span::raw(details.mac.clone())),
+        ];
+        for (i, address) in details.ipv4.iter().enumerate() {
+            let name = if i == 0 { "IPv4" } else { "" };
+            text.push(line(name, Span::raw(address.clone())));
+        }
+        for (i, address) in details.ipv6.iter().enumerate() {
+            let name = if i == 0 { "IPv6" } else { "" };
+            text.push(line(name, Span::raw(address.clone())));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(Block::default().title(format!("Details of {}", info.interface)))
+            .wrap(Wrap { trim: false });
+        f.render_widget(paragraph, rect);
+    }
+
+    /// Draws the received and sent bytes per second of the selected interface.
+    ///
+    /// The y-axis is scaled to the highest rate in the history.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn draw_chart<B: Backend>(&self, f: &mut Frame<B>, rect: Rect) {
+        let info = match self.get_selected_index() {
+            Some(i) => &self.interfaces[i],
+            None => return,
+        };
+        let history = match self.history.get(&info.interface) {
+            Some(x) => x,
+            None => return,
+        };
+
+        // The newest value is drawn at the right border
+        let offset = (HISTORY_LENGTH - history.len()) as f64;
+        let received: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .map(|(i, (rx, _))| (offset + i as f64, *rx))
+            .collect();
+        let sent: Vec<(f64, f64)> = history
+            .iter()
+            .enumerate()
+            .map(|(i, (_, tx))| (offset + i as f64, *tx))
+            .collect();
+        let max = history.iter().fold(1.0, |m, (rx, tx)| rx.max(*tx).max(m));
+        let (rx, tx) = self.get_rates(info);
+
+        let datasets = vec![
+            Dataset::default()
+                .name(format!("rx {}/s", util::to_humanreadable(rx as usize)))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Green))
+                .graph_type(GraphType::Line)
+                .data(&received),
+            Dataset::default()
+                .name(format!("tx {}/s", util::to_humanreadable(tx as usize)))
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(Color::Red))
+                .graph_type(GraphType::Line)
+                .data(&sent),
+        ];
+
+        let label = |value: f64| {
+            Span::styled(
+                util::to_humanreadable(value as usize) + "/s",
+                Style::default().add_modifier(Modifier::BOLD),
+            )
+        };
+        let chart = Chart::new(datasets)
+            .block(Block::default().title(format!("Throughput of {}", info.interface)))
+            .x_axis(Axis::default().bounds([0.0, HISTORY_LENGTH as f64]))
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .labels(vec![label(0.0), label(max / 2.0), label(max)])
+                    .bounds([0.0, max]),
+            )
+            .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)));
+        f.render_widget(chart, rect);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// Up and down select the interface, which is shown in the compact view.
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        let index = self.get_selected_index();
+        match (key, index) {
+            // Navigate by row
+            (Key::Down, Some(i)) if i + 1 < self.interfaces.len() => {
+                self.selected = Some(self.interfaces[i + 1].interface.clone());
+            }
+            (Key::Up, Some(i)) if i > 0 => {
+                self.selected = Some(self.interfaces[i - 1].interface.clone());
+            }
+            // Show or hide loopback and virtual interfaces, takes effect with the next measurement
+            (Key::Char('v'), _) => {
+                self.show_virtual = !self.show_virtual;
+            }
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, read_dir, File};
+use std::{io::BufRead, io::BufReader};
+
+use crate::util;
+
+const SYS_NODE: &str = "/sys/devices/system/node";
+
+/// Raw counters of a NUMA node, read from sysfs
+///
+/// See https://www.kernel.org/doc/html/latest/admin-guide/numastat.html
+#[derive(Default, Debug, Clone)]
+pub struct NumaNodeCounters {
+    pub id: usize,
+    /// Memory of the node in KiB
+    pub mem_total: u64,
+    /// Free memory of the node in KiB
+    pub mem_free: u64,
+    /// Pages successfully allocated on this node as intended
+    pub numa_hit: u64,
+    /// Pages allocated on this node although another node was intended
+    pub numa_miss: u64,
+}
+
+/// Memory usage and allocation rates of a NUMA node
+#[derive(Default, Debug, Clone)]
+pub struct NumaNode {
+    pub id: usize,
+    /// Memory of the node in KiB
+    pub mem_total: u64,
+    /// Free memory of the node in KiB
+    pub mem_free: u64,
+    /// numa_hit per second
+    pub hit_rate: f64,
+    /// numa_miss per second
+    pub miss_rate: f64,
+}
+
+/// Returns the ids of all NUMA nodes in ascending order.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn get_node_ids() -> Vec<usize> {
+    let dirs = match read_dir(SYS_NODE) {
+        Ok(x) => x,
+        Err(_) => return vec![],
+    };
+
+    let mut ids: Vec<usize> = dirs
+        .filter_map(|d| d.ok())
+        .filter_map(|d| {
+            d.file_name()
+                .to_str()
+                .and_then(|n| n.strip_prefix("node"))
+                .and_then(|id| id.parse().ok())
+        })
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Reads the memory and allocation counters of all NUMA nodes.
+///
+/// The node meminfo file has the format "Node 0 MemTotal:   123 kB",
+/// the numastat file the format "numa_hit 123".
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_node_counters() -> Vec<NumaNodeCounters> {
+    get_node_ids()
+        .into_iter()
+        .map(|id| {
+            let mut node = NumaNodeCounters {
+                id,
+                ..Default::default()
+            };
+
+            let meminfo = fs::read_to_string(format!("{}/node{}/meminfo", SYS_NODE, id));
+            for line in meminfo.unwrap_or_default().lines() {
+                let columns: Vec<&str> = line.split_whitespace().collect();
+                if columns.len() < 4 {
+                    continue;
+                }
+                let value = columns[3].parse().unwrap_or_default();
+                match columns[2] {
+                    "MemTotal:" => node.mem_total = value,
+                    "MemFree:" => node.mem_free = value,
+                    _ => {}
+                }
+            }
+
+            let numastat = fs::read_to_string(format!("{}/node{}/numastat", SYS_NODE, id));
+            for line in numastat.unwrap_or_default().lines() {
+                let mut columns = line.split_whitespace();
+                let name = columns.next().unwrap_or_default();
+                let value = columns
+                    .next()
+                    .map(|v| v.parse().unwrap_or_default())
+                    .unwrap_or_default();
+                match name {
+                    "numa_hit" => node.numa_hit = value,
+                    "numa_miss" => node.numa_miss = value,
+                    _ => {}
+                }
+            }
+
+            node
+        })
+        .collect()
+}
+
+/// Calculates the allocation rates of the NUMA nodes based on two measurements.
+///
+/// # Arguments
+///
+/// * 'previous' - previous counters
+/// * 'current' - current counters
+/// * 'elapsed' - elapsed time between both measurements in seconds
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn calculate_node_rates(
+    previous: &[NumaNodeCounters],
+    current: &[NumaNodeCounters],
+    elapsed: f64,
+) -> Vec<NumaNode> {
+    current
+        .iter()
+        .map(|node| {
+            let old = previous.iter().find(|p| p.id == node.id);
+            let rate = |value: fn(&NumaNodeCounters) -> u64| match old {
+                Some(old) => util::rate(value(old), value(node), elapsed),
+                None => 0.0,
+            };
+
+            NumaNode {
+                id: node.id,
+                mem_total: node.mem_total,
+                mem_free: node.mem_free,
+                hit_rate: rate(|n| n.numa_hit),
+                miss_rate: rate(|n| n.numa_miss),
+            }
+        })
+        .collect()
+}
+
+/// Reads the memory distribution of a process over the NUMA nodes.
+///
+/// Sums up the "N<node>=<pages>" entries of all mappings in '/proc/[pid]/numa_maps'
+/// and converts the pages to KiB using the "kernelpagesize_kB" of the mapping.
+///
+/// See https://man7.org/linux/man-pages/man7/numa.7.html
+///
+/// # Arguments
+///
+/// * 'pid' - A process id
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_process_distribution(pid: usize) -> BTreeMap<usize, u64> {
+    let mut distribution = BTreeMap::new();
+
+    let file = match File::open(format!("/proc/{}/numa_maps", pid)) {
+        Ok(f) => f,
+        Err(_) => return distribution,
+    };
+
+    for line in BufReader::new(file).lines() {
+        let row = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+
+        let mut page_size = 4;
+        let mut pages: HashMap<usize, u64> = HashMap::new();
+        for column in row.split_whitespace() {
+            if let Some(size) = column.strip_prefix("kernelpagesize_kB=") {
+                page_size = size.parse().unwrap_or(page_size);
+            } else if let Some((node, count)) =
+                column.strip_prefix('N').and_then(|c| c.split_once('='))
+            {
+                if let (Ok(node), Ok(count)) = (node.parse(), count.parse::<u64>()) {
+                    *pages.entry(node).or_default() += count;
+                }
+            }
+        }
+
+        for (node, count) in pages {
+            *distribution.entry(node).or_default() += count * page_size;
+        }
+    }
+
+    distribution
+}
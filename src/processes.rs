@@ -1,10 +1,13 @@
 use regex::Regex;
-use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::fs::{read_dir, File};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, read_dir, File};
+use std::io::{self, ErrorKind};
 use std::process::Command;
 use std::str;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
+use std::time::Instant;
 use std::{io::BufRead, io::BufReader};
 use std::{thread, time};
 use termion::event::Key;
@@ -13,30 +16,46 @@ use tui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     terminal::Frame,
-    text::Spans,
+    text::{Span, Spans},
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
 };
 
+use crate::mem;
+use crate::numa;
 use crate::util;
 
 /// CPUTime is used to store the most recent state of
-/// the CPU time of a thread, along with a timstamp of
-#[derive(Default, Clone, Copy)]
+/// the CPU time of a thread, along with the monotonic time it was read at
+#[derive(Clone, Copy)]
 pub struct CPUTime {
     exec_time: usize,
-    millis: usize,
+    time: Instant,
 }
 
 impl CPUTime {
     /// Returns a new CPUTime with the given values
-    pub fn new(exec_time: usize, millis: usize) -> Self {
-        let mut new: Self = Default::default();
-        new.exec_time = exec_time;
-        new.millis = millis;
-        new
+    pub fn new(exec_time: usize, time: Instant) -> Self {
+        Self { exec_time, time }
     }
 }
 
+/// Maximum number of OOM kill events which are remembered
+const OOM_EVENTS_LENGTH: usize = 50;
+
+/// An increment of the oom_kill counter in /proc/vmstat
+///
+/// The kernel doesn't tell which processes were killed without access to the kernel log,
+/// so all processes which vanished since the previous measurement are recorded.
+#[derive(Debug, Clone)]
+pub struct OomEvent {
+    /// Time of the measurement which noticed the kills
+    pub time: Instant,
+    /// Number of processes killed by the OOM killer
+    pub kills: u64,
+    /// Main threads of the processes which vanished at that moment
+    pub vanished: Vec<Process>,
+}
+
 /// ProcessList not only stores the list of processes (or threads),
 /// but also CPUTime's of the threads to make it possible to calculate
 /// the CPU usage.
@@ -44,6 +63,17 @@ impl CPUTime {
 pub struct ProcessList {
     cpu_times: HashMap<usize, CPUTime>,
     pub processes: Vec<Process>,
+    /// Whether the expensive PSS/USS values are read from smaps_rollup
+    read_smaps: Arc<AtomicBool>,
+    /// Process whose details are shown, 0 if none
+    details_pid: Arc<AtomicUsize>,
+    /// Pid and NUMA distribution in KiB by node of the process whose details are shown,
+    /// empty on single node systems
+    pub numa_distribution: Option<(usize, BTreeMap<usize, u64>)>,
+    /// Value of the oom_kill counter at the previous measurement
+    oom_kills: Option<u64>,
+    /// Noticed OOM kills, the newest event is the last one
+    pub oom_events: Vec<OomEvent>,
 }
 
 impl ProcessList {
@@ -62,14 +92,19 @@ impl ProcessList {
     ///
     /// This function won't panic.
     pub fn clone(&mut self) -> Self {
-        let mut new: Self = Default::default();
-        new.cpu_times = self.cpu_times.clone();
         let mut processes: Vec<Process> = Default::default();
         for p in self.processes.iter() {
             processes.push(p.clone());
         }
-        new.processes = processes;
-        new
+        Self {
+            cpu_times: self.cpu_times.clone(),
+            processes,
+            read_smaps: self.read_smaps.clone(),
+            details_pid: self.details_pid.clone(),
+            numa_distribution: self.numa_distribution.clone(),
+            oom_kills: self.oom_kills,
+            oom_events: self.oom_events.clone(),
+        }
     }
 
     /// Update everything contained by the list of processes
@@ -81,7 +116,7 @@ impl ProcessList {
     ///
     /// This function won't panic.
     pub fn update(&mut self) {
-        self.processes = Default::default();
+        let previous = std::mem::take(&mut self.processes);
 
         let re_pid = Regex::new("^/proc/(?P<pid>[0-9]+)$").unwrap();
         let re_tid = Regex::new("^/proc/[0-9]+/task/(?P<tid>[0-9]+)$").unwrap();
@@ -164,9 +199,126 @@ impl ProcessList {
                     .push(Process::new(pid, tid, &mut self.cpu_times))
             }
         }
+
+        if self.read_smaps.load(AtomicOrdering::Relaxed) {
+            self.update_smaps();
+        }
+
+        // numa_maps of large processes is expensive to parse, only read the shown one
+        self.numa_distribution = match self.details_pid.load(AtomicOrdering::Relaxed) {
+            0 => None,
+            pid if numa::get_node_ids().len() > 1 => {
+                Some((pid, numa::read_process_distribution(pid)))
+            }
+            pid => Some((pid, BTreeMap::new())),
+        };
+
+        self.detect_oom_kills(previous);
+    }
+
+    /// Records an OOM event if the oom_kill counter in '/proc/vmstat' increased
+    ///
+    /// The processes of the previous measurement which don't exist anymore are
+    /// stored with the event. The counter is available since Linux 4.13.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - the processes (and threads) of the previous measurement
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn detect_oom_kills(&mut self, previous: Vec<Process>) {
+        let oom_kills = match mem::read_vmstat().get("oom_kill") {
+            Some(x) => *x,
+            None => return,
+        };
+
+        let kills = oom_kills.saturating_sub(self.oom_kills.unwrap_or(oom_kills));
+        self.oom_kills = Some(oom_kills);
+        if kills == 0 {
+            return;
+        }
+
+        let vanished = previous
+            .into_iter()
+            .filter(|old| old.pid == old.tid)
+            .filter(|old| !self.processes.iter().any(|p| p.pid == old.pid))
+            .collect();
+
+        self.oom_events.push(OomEvent {
+            time: Instant::now(),
+            kills,
+            vanished,
+        });
+        if self.oom_events.len() > OOM_EVENTS_LENGTH {
+            self.oom_events.remove(0);
+        }
+    }
+
+    /// Update the PSS and USS of all processes
+    ///
+    /// Reading '/proc/[pid]/smaps_rollup' is expensive since the kernel has to walk
+    /// all memory mappings, so it is read once per process and the values are shared
+    /// by all threads of the process.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn update_smaps(&mut self) {
+        let mut smaps: HashMap<usize, (usize, usize)> = HashMap::new();
+
+        for p in self.processes.iter_mut() {
+            let (pss, uss) = *smaps
+                .entry(p.pid)
+                .or_insert_with(|| read_smaps_rollup(p.pid));
+            p.pss = pss;
+            p.uss = uss;
+        }
     }
 }
 
+/// Reads the proportional and unique set size of a process
+///
+/// Returns (PSS, USS) in KiB read from '/proc/[pid]/smaps_rollup'. The USS is the sum of all
+/// private pages. Both values are 0 if the file isn't readable (e.g. processes of other users).
+///
+/// See https://www.kernel.org/doc/html/latest/filesystems/proc.html
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_smaps_rollup(pid: usize) -> (usize, usize) {
+    let path = format!("/proc/{}/smaps_rollup", pid);
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return (0, 0),
+    };
+
+    let mut pss = 0;
+    let mut uss = 0;
+    for line in BufReader::new(file).lines() {
+        let row = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+
+        let mut columns = row.split_whitespace();
+        let name = columns.next().unwrap_or_default();
+        let value: usize = columns
+            .next()
+            .map(|v| v.parse().unwrap_or_default())
+            .unwrap_or_default();
+
+        match name {
+            "Pss:" => pss = value,
+            "Private_Clean:" | "Private_Dirty:" | "Private_Hugetlb:" => uss += value,
+            _ => {}
+        }
+    }
+    (pss, uss)
+}
+
 /// Process is used to store information of one
 /// Process (or thread)
 #[derive(Default, Debug, Clone)]
@@ -177,11 +329,30 @@ pub struct Process {
     pub state: String,
     pub parent_pid: usize,
     pub tid: usize,
+    /// Anonymous resident memory (RssAnon) in KiB
     pub memory: usize,
+    /// Resident set size (VmRSS) in KiB
+    pub rss: usize,
+    /// Virtual memory size (VmSize) in KiB
+    pub virtual_size: usize,
+    /// Resident file mappings (RssFile) in KiB
+    pub rss_file: usize,
+    /// Resident shared memory (RssShmem) in KiB
+    pub rss_shmem: usize,
+    /// Swapped out anonymous memory (VmSwap) in KiB
+    pub swap: usize,
+    /// Proportional set size in KiB, only read if the column is visible
+    pub pss: usize,
+    /// Unique set size in KiB, only read if the column is visible
+    pub uss: usize,
     pub command: String,
     pub threads: usize,
     pub user: String,
     pub nice: i8,
+    /// Badness score of the OOM killer, the process with the highest score is killed first
+    pub oom_score: usize,
+    /// Adjustment of the OOM score between -1000 (never kill) and 1000
+    pub oom_score_adj: i16,
     cpu_time: usize,
     pub cpu_usage: f32,
 }
@@ -201,9 +372,11 @@ impl Process {
     ///
     /// This function won't panic.
     pub fn new(pid: usize, tid: usize, cpu_times: &mut HashMap<usize, CPUTime>) -> Self {
-        let mut new: Self = Default::default();
-        new.pid = pid;
-        new.tid = tid;
+        let mut new = Self {
+            pid,
+            tid,
+            ..Default::default()
+        };
         new.update(cpu_times);
         new
     }
@@ -225,6 +398,7 @@ impl Process {
         self.update_command();
         self.update_user();
         self.update_stat();
+        self.update_oom();
         self.update_cpu_usage(cpu_times);
     }
 
@@ -238,6 +412,11 @@ impl Process {
     /// * `name`
     /// * `umask`
     /// * `memory`
+    /// * `rss`
+    /// * `virtual_size`
+    /// * `rss_file`
+    /// * `rss_shmem`
+    /// * `swap`
     ///
     /// # Panic
     ///
@@ -263,18 +442,28 @@ impl Process {
             let value: String = vec[1].trim().to_string();
             let name: &str = vec[0].trim();
 
+            // value.len() - 3 cuts of " kB" at the end of the memory values
+            let kib = || -> usize {
+                value
+                    .get(0..value.len().saturating_sub(3))
+                    .unwrap_or_default()
+                    .trim()
+                    .parse()
+                    .unwrap_or_default()
+            };
+
             // https://man7.org/linux/man-pages/man5/proc.5.html
             match name {
-                "Name" => (*self).name = value,
-                "Umask" => (*self).umask = value,
-                "RssAnon" => {
-                    // value.len() - 3 cuts of " KB" at the end of the string
-                    let value = match value[0..value.len() - 3].parse::<usize>() {
-                        Ok(x) => x,
-                        Err(_) => 0,
-                    };
-                    (*self).memory = value;
-                    // 'RssAnon" is the last value that is needed -> break
+                "Name" => self.name = value,
+                "Umask" => self.umask = value,
+                "VmSize" => self.virtual_size = kib(),
+                "VmRSS" => self.rss = kib(),
+                "RssAnon" => self.memory = kib(),
+                "RssFile" => self.rss_file = kib(),
+                "RssShmem" => self.rss_shmem = kib(),
+                "VmSwap" => {
+                    self.swap = kib();
+                    // 'VmSwap" is the last value that is needed -> break
                     break;
                 }
                 _ => continue,
@@ -304,15 +493,12 @@ impl Process {
         };
         let reader = BufReader::new(filehandler);
 
-        let mut result: String = String::from("");
-        for line in reader.lines() {
-            result = match line {
-                Ok(x) => x,
-                Err(_) => break,
-            };
-            break;
-        }
-        (*self).command = result;
+        // only the first line is of interest
+        let result = match reader.lines().next() {
+            Some(Ok(x)) => x,
+            _ => String::from(""),
+        };
+        self.command = result;
     }
 
     /// Update the Process (or thread) user
@@ -329,7 +515,7 @@ impl Process {
     /// This function won't panic.
     fn update_user(&mut self) {
         let mut command = Command::new("stat");
-        command.args(&[
+        command.args([
             "-c",
             "'%U",
             &format!("/proc/{}/task/{}", self.pid, self.tid)[..],
@@ -344,7 +530,7 @@ impl Process {
             Err(_) => "Invalid",
         };
 
-        (*self).user = response[1..response.len() - 1].to_string();
+        self.user = response[1..response.len() - 1].to_string();
     }
 
     /// Update the Process (or thread) stat
@@ -384,28 +570,34 @@ impl Process {
         let metrics: Vec<&str> = tmp[1].split(" ").collect();
 
         // https://man7.org/linux/man-pages/man5/proc.5.html
-        (*self).state = metrics[0].to_string();
-        (*self).parent_pid = match metrics[1].parse::<usize>() {
-            Ok(x) => x,
-            Err(_) => 0,
-        };
-        (*self).nice = match metrics[16].parse::<i8>() {
-            Ok(x) => x,
-            Err(_) => 0,
-        };
-        (*self).threads = match metrics[17].parse::<usize>() {
-            Ok(x) => x,
-            Err(_) => 0,
-        };
-        let utime = match metrics[11].parse::<usize>() {
-            Ok(x) => x,
-            Err(_) => 0,
-        };
-        let stime = match metrics[12].parse::<usize>() {
-            Ok(x) => x,
-            Err(_) => 0,
-        };
-        (*self).cpu_time = utime + stime;
+        self.state = metrics[0].to_string();
+        self.parent_pid = metrics[1].parse::<usize>().unwrap_or_default();
+        self.nice = metrics[16].parse::<i8>().unwrap_or_default();
+        self.threads = metrics[17].parse::<usize>().unwrap_or_default();
+        let utime = metrics[11].parse::<usize>().unwrap_or_default();
+        let stime = metrics[12].parse::<usize>().unwrap_or_default();
+        self.cpu_time = utime + stime;
+    }
+
+    /// Update the OOM score of the Process (or thread)
+    ///
+    /// This function reads '/proc/[pid]/task/[tid]/oom_score' and 'oom_score_adj'.
+    ///
+    /// # Updates the following attributes:
+    ///
+    /// * `oom_score`
+    /// * `oom_score_adj`
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn update_oom(&mut self) {
+        let path = format!("/proc/{}/task/{}", self.pid, self.tid);
+        let read =
+            |name: &str| fs::read_to_string(format!("{}/{}", path, name)).unwrap_or_default();
+
+        self.oom_score = read("oom_score").trim().parse().unwrap_or_default();
+        self.oom_score_adj = read("oom_score_adj").trim().parse().unwrap_or_default();
     }
 
     /// Calculates the cpu usage
@@ -425,34 +617,41 @@ impl Process {
     ///
     /// This function won't panic.
     fn update_cpu_usage(&mut self, cpu_times: &mut HashMap<usize, CPUTime>) {
-        // get cpu time of the process (or thread) from last time it was updated
-        let old_cpu_times = match cpu_times.get(&self.tid) {
-            Some(x) => *x,
-            None => Default::default(),
+        let now = Instant::now();
+
+        // store the current cpu time and get the one from last time it was updated
+        let old_cpu_times = cpu_times.insert(self.tid, CPUTime::new(self.cpu_time, now));
+
+        // a new process (or thread) has no usage yet, the first measurement is the reference
+        let old_cpu_times = match old_cpu_times {
+            Some(x) => x,
+            None => {
+                self.cpu_usage = 0.0;
+                return;
+            }
         };
+
         // calculate the elapsed cpu time of the process (or thread) in Linux ticks (default: 100/s)
-        let delta_cpu_time: f32 = (self.cpu_time - old_cpu_times.exec_time) as f32;
+        // the counter is reset if the tid was reused by a new thread
+        let delta_cpu_time: f32 = self.cpu_time.saturating_sub(old_cpu_times.exec_time) as f32;
         // calculate the (real) elapsed time (in seconds)
-        let delta_real_time: f32 =
-            ((util::get_millis() - old_cpu_times.millis) as f64 / 1000.0) as f32;
-
-        // update the values of the HashMap
-        match cpu_times.get_mut(&self.tid) {
-            Some(x) => {
-                // if there was already a value for the process (or thread),
-                // just overwrite it with the current one
-                *x = CPUTime::new(self.cpu_time, util::get_millis());
-            }
-            None => {
-                // otherwise insert a new one
-                cpu_times.insert(self.tid, CPUTime::new(self.cpu_time, util::get_millis()));
-                ()
-            }
-        }
+        let delta_real_time: f32 = now.duration_since(old_cpu_times.time).as_secs_f32();
 
         // Because delta_cpu_time is calculated in Linux ticks (default: 100/s),
         // it is not necessary to multiply 100 to the result to get a percentage value.
-        (*self).cpu_usage = delta_cpu_time / delta_real_time;
+        self.cpu_usage = match delta_real_time > 0.0 {
+            true => delta_cpu_time / delta_real_time,
+            false => 0.0,
+        };
+    }
+}
+
+/// Formats the age of an event like "42s ago", "5m ago" or "3h ago".
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
     }
 }
 
@@ -460,15 +659,25 @@ impl Process {
 ///
 /// The ProcessList is created once and updated on every iteration.
 ///
+/// # Arguments
+///
+/// * `read_smaps` - whether PSS and USS should be read, can be changed while the thread runs
+/// * `details_pid` - process whose NUMA distribution is read, 0 if none
+///
 /// # Panic
 ///
 /// This function won't panic.
-pub fn init_data_collection_thread() -> mpsc::Receiver<ProcessList> {
+pub fn init_data_collection_thread(
+    read_smaps: Arc<AtomicBool>,
+    details_pid: Arc<AtomicUsize>,
+) -> mpsc::Receiver<ProcessList> {
     let (tx, rx) = mpsc::channel();
 
     let dur = time::Duration::from_millis(2500);
 
     let mut pl: ProcessList = ProcessList::new();
+    pl.read_smaps = read_smaps;
+    pl.details_pid = details_pid;
 
     // Thread for the data collection
     let _ = thread::spawn(move || loop {
@@ -481,11 +690,89 @@ pub fn init_data_collection_thread() -> mpsc::Receiver<ProcessList> {
     rx
 }
 
+/// Names of the columns by column id
+const COLUMN_NAMES: [&str; 21] = [
+    "PID", "PPID", "TID", "User", "Umask", "Threads", "Name", "State", "Nice", "CPU", "Mem", "CMD",
+    "RSS", "VSZ", "RssFile", "Shmem", "Swap", "PSS", "USS", "OOM", "OOMAdj",
+];
+
+/// Widths of the columns by column id, the command column takes the remaining space
+const COLUMN_WIDTHS: [u16; 21] = [
+    8, 8, 8, 15, 6, 7, 30, 6, 5, 8, 9, 0, 9, 9, 9, 9, 9, 9, 9, 5, 6,
+];
+
+/// Id of the command column, which is always the last column
+const COMMAND_COLUMN: usize = 11;
+
+/// Ids of the OOM score columns, shown behind the memory columns
+const OOM_COLUMNS: [usize; 2] = [19, 20];
+
+/// Defines which memory columns are shown in the process table.
+#[derive(PartialEq, Clone, Copy)]
+enum MemoryColumns {
+    /// Anonymous resident memory only
+    Basic,
+    /// Resident, virtual, file backed, shared and swapped memory from the status file
+    Resident,
+    /// Resident, proportional, unique and swapped memory, reads smaps_rollup
+    Proportional,
+}
+
+impl MemoryColumns {
+    /// Returns the next set of memory columns
+    fn next(&self) -> MemoryColumns {
+        match *self {
+            MemoryColumns::Basic => MemoryColumns::Resident,
+            MemoryColumns::Resident => MemoryColumns::Proportional,
+            MemoryColumns::Proportional => MemoryColumns::Basic,
+        }
+    }
+
+    /// Returns the ids of the memory columns
+    fn get_columns(&self) -> &[usize] {
+        match *self {
+            MemoryColumns::Basic => &[10],
+            MemoryColumns::Resident => &[12, 13, 14, 15, 16],
+            MemoryColumns::Proportional => &[12, 17, 18, 16],
+        }
+    }
+}
+
+/// Information shown in the details popup of a process
+struct ProcessDetails {
+    process: Process,
+    /// Memory of the process per NUMA node in KiB, empty on single node systems
+    numa_distribution: BTreeMap<usize, u64>,
+}
+
+impl ProcessDetails {
+    /// Creates the details of a process.
+    ///
+    /// The NUMA distribution is read by the data collection thread, it is taken from the
+    /// process list if it belongs to the process.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn new(process: &Process, process_list: &ProcessList) -> Self {
+        let numa_distribution = match &process_list.numa_distribution {
+            Some((pid, distribution)) if *pid == process.pid => distribution.clone(),
+            _ => BTreeMap::new(),
+        };
+
+        Self {
+            process: process.clone(),
+            numa_distribution,
+        }
+    }
+}
+
 #[derive(PartialEq)]
 /// Defines whether the popup input is for the niceness or the filter value.
 enum InputMode {
     Niceness,
     Filter,
+    OomScoreAdj,
 }
 
 /// Stores the state data of the widget.
@@ -516,11 +803,25 @@ pub struct ProcessesWidget {
     input: String,
     /// Store input mode.
     input_mode: InputMode,
+    /// Error of the last applied input, shown in the popup.
+    input_error: Option<String>,
+    /// Currently shown memory columns.
+    memory_columns: MemoryColumns,
+    /// Tells the data collection thread whether PSS and USS are needed.
+    read_smaps: Arc<AtomicBool>,
+    /// Tells the data collection thread whose NUMA distribution is needed.
+    details_pid: Arc<AtomicUsize>,
+    /// Details of the selected process, shown in a popup if set.
+    details: Option<ProcessDetails>,
+    /// Process whose sockets should be shown in the connections widget.
+    connections_request: Option<usize>,
 }
 
 impl ProcessesWidget {
     /// Creates new process widget with default values.
     pub fn new() -> Self {
+        let read_smaps = Arc::new(AtomicBool::new(false));
+        let details_pid = Arc::new(AtomicUsize::new(0));
         let mut a = Self {
             table_state: TableState::default(),
             item_index: 0,
@@ -528,18 +829,88 @@ impl ProcessesWidget {
             sort_index: 9,
             sort_descending: true,
             process_list: Default::default(),
-            dc_thread: init_data_collection_thread(),
+            dc_thread: init_data_collection_thread(read_smaps.clone(), details_pid.clone()),
             popup_open: false,
             input: String::from(""),
             input_mode: InputMode::Niceness,
+            input_error: None,
             filter_index: None,
             filter_value_str: String::from(""),
             filter_value_usize: 0,
+            memory_columns: MemoryColumns::Basic,
+            read_smaps,
+            details_pid,
+            details: None,
+            connections_request: None,
         };
         a.table_state.select(Some(0));
         a
     }
 
+    /// Returns the ids of the visible columns in the order they are displayed.
+    fn visible_columns(&self) -> Vec<usize> {
+        let mut columns: Vec<usize> = (0..10).collect();
+        columns.extend(self.memory_columns.get_columns());
+        columns.extend(OOM_COLUMNS);
+        columns.push(COMMAND_COLUMN);
+        columns
+    }
+
+    /// Shows the next set of memory columns.
+    ///
+    /// PSS and USS are only read by the data collection thread while they are visible.
+    /// If the selected or sorted column gets hidden, the CPU column is used instead.
+    fn switch_memory_columns(&mut self) {
+        self.memory_columns = self.memory_columns.next();
+        self.read_smaps.store(
+            self.memory_columns == MemoryColumns::Proportional,
+            AtomicOrdering::Relaxed,
+        );
+
+        let visible = self.visible_columns();
+        if !visible.contains(&self.column_index) {
+            self.column_index = 9;
+        }
+        if !visible.contains(&self.sort_index) {
+            self.sort_index = 9;
+            self.sort();
+        }
+    }
+
+    /// Returns the cell of a process for the given column id.
+    fn get_cell(p: &Process, column: usize) -> Cell<'static> {
+        let memory = |kib: usize| format!("{: >9}", util::to_humanreadable(kib * 1024));
+        match column {
+            0 => Cell::from(format!("{: >7}", p.pid)),
+            1 => Cell::from(format!("{: >7}", p.parent_pid)),
+            2 => Cell::from(format!("{: >7}", p.tid)),
+            3 => Cell::from(p.user.to_string()),
+            4 => Cell::from(format!("{: >5}", p.umask)),
+            5 => Cell::from(format!("{: >7}", p.threads)),
+            6 => Cell::from(p.name.to_string()),
+            7 => Cell::from(p.state.to_string()),
+            8 => Cell::from(format!("{: >4}", p.nice)),
+            9 => Cell::from(format!("{: >7}", format!("{:3.2}%", p.cpu_usage))),
+            10 => Cell::from(memory(p.memory)),
+            11 => Cell::from(p.command.to_string()),
+            12 => Cell::from(memory(p.rss)),
+            13 => Cell::from(memory(p.virtual_size)),
+            14 => Cell::from(memory(p.rss_file)),
+            15 => Cell::from(memory(p.rss_shmem)),
+            16 => Cell::from(memory(p.swap)),
+            17 => Cell::from(memory(p.pss)),
+            18 => Cell::from(memory(p.uss)),
+            19 => Cell::from(format!("{: >4}", p.oom_score)),
+            // processes protected from the OOM killer are highlighted
+            20 => Cell::from(format!("{: >5}", p.oom_score_adj)).style(match p.oom_score_adj {
+                -1000 => Style::default().fg(Color::Green),
+                x if x > 0 => Style::default().fg(Color::Yellow),
+                _ => Style::default(),
+            }),
+            _ => Cell::from(""),
+        }
+    }
+
     /// Sorts process data ascending or descending by the current selected column.
     fn sort(&mut self) {
         let sort_index = self.sort_index;
@@ -564,6 +935,15 @@ impl ProcessesWidget {
                     .unwrap_or(Ordering::Equal),
                 10 => a.memory.partial_cmp(&b.memory).unwrap_or(Ordering::Equal),
                 11 => a.command.partial_cmp(&b.command).unwrap_or(Ordering::Equal),
+                12 => a.rss.cmp(&b.rss),
+                13 => a.virtual_size.cmp(&b.virtual_size),
+                14 => a.rss_file.cmp(&b.rss_file),
+                15 => a.rss_shmem.cmp(&b.rss_shmem),
+                16 => a.swap.cmp(&b.swap),
+                17 => a.pss.cmp(&b.pss),
+                18 => a.uss.cmp(&b.uss),
+                19 => a.oom_score.cmp(&b.oom_score),
+                20 => a.oom_score_adj.cmp(&b.oom_score_adj),
                 _ => Ordering::Equal,
             };
             if sort_descending {
@@ -576,6 +956,8 @@ impl ProcessesWidget {
 
     /// Filters process data by the current selected filter column and the supplied value.
     fn filter(&mut self, p: &Process) -> bool {
+        // memory values are in KiB
+        let minimum_kib = self.filter_value_usize.saturating_mul(1024);
         match self.filter_index {
             // Numbers
             Some(0) => p.pid == self.filter_value_usize,
@@ -588,6 +970,16 @@ impl ProcessesWidget {
             Some(6) => p.name.contains(&self.filter_value_str),
             Some(7) => p.state.contains(&self.filter_value_str),
             Some(11) => p.command.contains(&self.filter_value_str),
+            // Minimum values, memory in MiB
+            Some(10) => p.memory >= minimum_kib,
+            Some(12) => p.rss >= minimum_kib,
+            Some(13) => p.virtual_size >= minimum_kib,
+            Some(14) => p.rss_file >= minimum_kib,
+            Some(15) => p.rss_shmem >= minimum_kib,
+            Some(16) => p.swap >= minimum_kib,
+            Some(17) => p.pss >= minimum_kib,
+            Some(18) => p.uss >= minimum_kib,
+            Some(19) => p.oom_score >= self.filter_value_usize,
             _ => true,
         }
     }
@@ -597,16 +989,21 @@ impl ProcessesWidget {
         // Recv data from the data collector thread
         let processes_info = self.dc_thread.try_recv();
 
-        match processes_info {
-            Ok(mut x) => {
-                x.processes.retain(|p| self.filter(p));
+        if let Ok(mut x) = processes_info {
+            x.processes.retain(|p| self.filter(p));
 
-                if !self.popup_open {
-                    self.process_list = x;
-                    self.sort();
+            if !self.popup_open {
+                self.process_list = x;
+                self.sort();
+            }
+
+            // Refresh the details of the shown process, keep the old ones if it exited
+            if let Some(details) = &self.details {
+                let tid = details.process.tid;
+                if let Some(p) = self.process_list.processes.iter().find(|p| p.tid == tid) {
+                    self.details = Some(ProcessDetails::new(p, &self.process_list));
                 }
             }
-            Err(_) => (),
         }
     }
 
@@ -629,18 +1026,15 @@ impl ProcessesWidget {
             .add_modifier(Modifier::REVERSED);
         let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
 
+        let columns = self.visible_columns();
+
         // Create new header row in table
-        let header_cells = [
-            "PID", "PPID", "TID", "User", "Umask", "Threads", "Name", "State", "Nice", "CPU",
-            "Mem", "CMD",
-        ]
-        .iter()
-        .enumerate()
-        .map(|(i, h)| {
+        let header_cells = columns.iter().map(|&i| {
             if i == self.column_index {
-                Cell::from(*h).style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+                Cell::from(COLUMN_NAMES[i])
+                    .style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
             } else {
-                Cell::from(*h)
+                Cell::from(COLUMN_NAMES[i])
             }
         });
 
@@ -648,55 +1042,232 @@ impl ProcessesWidget {
 
         // Populate rows of table
         let rows = self.process_list.processes.iter().map(|p| {
-            let mut cells = Vec::new();
-            cells.push(Cell::from(format!("{: >7}", p.pid)));
-            cells.push(Cell::from(format!("{: >7}", p.parent_pid)));
-            cells.push(Cell::from(format!("{: >7}", p.tid)));
-            cells.push(Cell::from(p.user.to_string()));
-            cells.push(Cell::from(format!("{: >5}", p.umask)));
-            cells.push(Cell::from(format!("{: >7}", p.threads)));
-            cells.push(Cell::from(p.name.to_string()));
-            cells.push(Cell::from(p.state.to_string()));
-            cells.push(Cell::from(format!("{: >4}", p.nice)));
-            cells.push(Cell::from(format!(
-                "{: >7}",
-                format!("{:3.2}%", p.cpu_usage)
-            )));
-            cells.push(Cell::from(format!(
-                "{: >9}",
-                util::to_humanreadable(p.memory * 1024)
-            )));
-            cells.push(Cell::from(p.command.to_string()));
+            let cells = columns.iter().map(|&i| Self::get_cell(p, i));
             Row::new(cells).height(1)
         });
 
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|&i| match i {
+                COMMAND_COLUMN => Constraint::Min(1),
+                _ => Constraint::Length(COLUMN_WIDTHS[i]),
+            })
+            .collect();
+
         // Create new table
         let table = Table::new(rows)
             .header(header)
             .highlight_style(selected_style)
-            .widths(&[
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Length(8),
-                Constraint::Length(15),
-                Constraint::Length(6),
-                Constraint::Length(7),
-                Constraint::Length(30),
-                Constraint::Length(6),
-                Constraint::Length(5),
-                Constraint::Length(8),
-                Constraint::Length(9),
-                Constraint::Min(1),
-            ])
+            .widths(&widths)
             .block(block);
         f.render_stateful_widget(table, rect, &mut self.table_state);
 
         // Draw popup over table
         if self.popup_open {
             self.draw_popup(f, &rect);
+        } else if self.details.is_some() {
+            self.draw_details(f, &rect);
         }
     }
 
+    /// Draw the details of the selected process over the table.
+    fn draw_details<B: Backend>(&self, f: &mut Frame<B>, rect: &Rect) {
+        let details = match &self.details {
+            Some(x) => x,
+            None => return,
+        };
+        let p = &details.process;
+        let memory = |kib: usize| util::to_humanreadable(kib * 1024);
+
+        let mut text = vec![
+            Spans::from(format!(
+                "PID {}  TID {}  PPID {}",
+                p.pid, p.tid, p.parent_pid
+            )),
+            Spans::from(format!("Name    {}", p.name)),
+            Spans::from(format!("User    {}", p.user)),
+            Spans::from(format!("State   {}  Nice {}", p.state, p.nice)),
+            Spans::from(format!("Command {}", p.command)),
+            Spans::default(),
+            Spans::from(format!(
+                "RSS {}  VSZ {}  Swap {}",
+                memory(p.rss),
+                memory(p.virtual_size),
+                memory(p.swap)
+            )),
+            Spans::from(format!(
+                "Anon {}  File {}  Shmem {}",
+                memory(p.memory),
+                memory(p.rss_file),
+                memory(p.rss_shmem)
+            )),
+        ];
+        if self.memory_columns == MemoryColumns::Proportional {
+            text.push(Spans::from(format!(
+                "PSS {}  USS {}",
+                memory(p.pss),
+                memory(p.uss)
+            )));
+        }
+
+        if !details.numa_distribution.is_empty() {
+            text.push(Spans::default());
+            text.push(Spans::from("NUMA memory distribution"));
+            let total: u64 = details.numa_distribution.values().sum();
+            for (node, kib) in &details.numa_distribution {
+                text.push(Spans::from(format!(
+                    "  node{} {: >10} {: >5.1}%",
+                    node,
+                    util::to_humanreadable(*kib as usize * 1024),
+                    *kib as f64 / total.max(1) as f64 * 100.0
+                )));
+            }
+        }
+
+        text.push(Spans::default());
+        text.push(Spans::from("Press Enter or ESC to close"));
+
+        // Center the popup, the borders need two additional lines
+        let height = (text.len() as u16 + 2).min(rect.height);
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(20),
+                ]
+                .as_ref(),
+            )
+            .split(*rect);
+        let popup = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length((rect.height - height) / 2),
+                    Constraint::Length(height),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(horizontal[1]);
+
+        let block = Block::default()
+            .style(Style::default().fg(Color::Yellow))
+            .title("Details")
+            .borders(Borders::ALL);
+        let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+        f.render_widget(Clear, popup[1]);
+        f.render_widget(paragraph, popup[1]);
+    }
+
+    /// Draws the processes the OOM killer would choose first and the noticed OOM kills.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw_oom_events<B: Backend>(&self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(7), Constraint::Min(1)].as_ref())
+            .split(inner);
+
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+        let memory = |kib: usize| format!("{: >9}", util::to_humanreadable(kib * 1024));
+        let widths = [
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(15),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(6),
+        ];
+
+        // Processes with the highest OOM score, threads share the score of their process
+        let mut candidates: Vec<&Process> = self
+            .process_list
+            .processes
+            .iter()
+            .filter(|p| p.pid == p.tid)
+            .collect();
+        candidates.sort_by_key(|p| Reverse(p.oom_score));
+        let rows = candidates.iter().take(5).map(|p| {
+            Row::new(vec![
+                Cell::from(format!("{: >7}", p.pid)),
+                Cell::from(p.user.to_string()),
+                Cell::from(p.name.to_string()),
+                Cell::from(memory(p.rss)),
+                Self::get_cell(p, 19),
+                Self::get_cell(p, 20),
+            ])
+        });
+        let header =
+            Row::new(vec!["PID", "User", "Name", "RSS", "OOM", "OOMAdj"]).style(header_style);
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title("Next OOM victims"))
+            .widths(&widths[2..]);
+        f.render_widget(table, chunks[0]);
+
+        // Newest events first, one row per vanished process
+        let mut rows: Vec<Row> = Vec::new();
+        for event in self.process_list.oom_events.iter().rev() {
+            let time = format!("{: >8}", format_age(event.time.elapsed().as_secs()));
+            let kills = format!("{: >5}", event.kills);
+            if event.vanished.is_empty() {
+                rows.push(Row::new(vec![
+                    Cell::from(time.clone()),
+                    Cell::from(kills.clone()),
+                    Cell::from(""),
+                    Cell::from(""),
+                    Cell::from("unknown, started and killed between two samples"),
+                ]));
+            }
+            for (i, p) in event.vanished.iter().enumerate() {
+                // The time and number of kills are only shown in the first row of an event
+                let (time, kills) = match i {
+                    0 => (time.clone(), kills.clone()),
+                    _ => (String::new(), String::new()),
+                };
+                rows.push(Row::new(vec![
+                    Cell::from(time),
+                    Cell::from(kills),
+                    Cell::from(format!("{: >7}", p.pid)),
+                    Cell::from(p.user.to_string()),
+                    Cell::from(p.name.to_string()),
+                    Cell::from(memory(p.rss)),
+                    Self::get_cell(p, 19),
+                    Self::get_cell(p, 20),
+                ]));
+            }
+        }
+
+        let title = match self.process_list.oom_events.is_empty() {
+            true => "OOM kills (none noticed since start)",
+            false => "OOM kills (processes which vanished at that moment)",
+        };
+        let header = Row::new(vec![
+            "Time", "Kills", "PID", "User", "Name", "RSS", "OOM", "OOMAdj",
+        ])
+        .style(header_style);
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().title(title))
+            .widths(&widths);
+        f.render_widget(table, chunks[1]);
+    }
+
     /// Draw popup over the table.
     fn draw_popup<B: Backend>(&mut self, f: &mut Frame<B>, rect: &Rect) {
         // Define postion of popup
@@ -738,10 +1309,21 @@ impl ProcessesWidget {
             )
             .split(horizontal[1]);
 
+        // Describe the input of the minimum filters
+        let hint = match self.input_mode {
+            InputMode::Filter if self.column_index == 19 => "Minimum OOM score",
+            InputMode::Filter if self.is_minimum_column(self.column_index) => "Minimum in MiB",
+            InputMode::OomScoreAdj => "-1000 (never kill) to 1000 (kill first)",
+            _ => "",
+        };
+        let error = match &self.input_error {
+            Some(e) => Spans::from(Span::styled(e.clone(), Style::default().fg(Color::Red))),
+            None => Spans::default(),
+        };
         let text = vec![
-            Spans::default(),
-            Spans::from(format!("{}", self.input)),
-            Spans::default(),
+            Spans::from(hint),
+            Spans::from(self.input.to_string()),
+            error,
             Spans::default(),
             Spans::from("CTRL-x to cancel"),
             Spans::from("Press Enter to apply"),
@@ -757,49 +1339,72 @@ impl ProcessesWidget {
     }
     /// Handles keyboard input
     pub fn handle_input(&mut self, key: Key) {
-        if !self.popup_open {
+        if self.details.is_some() {
+            // Close the details popup
+            if let Key::Char('\n') | Key::Esc | Key::Ctrl('x') = key {
+                self.details = None;
+                self.details_pid.store(0, AtomicOrdering::Relaxed);
+            }
+        } else if !self.popup_open {
             match key {
                 // Navigate by row
-                Key::Down => {
-                    if self.item_index < self.process_list.processes.len() - 1 {
-                        self.item_index += 1;
-                        self.table_state.select(Some(self.item_index));
-                    }
+                Key::Down if self.item_index < self.process_list.processes.len() - 1 => {
+                    self.item_index += 1;
+                    self.table_state.select(Some(self.item_index));
                 }
-                Key::Up => {
-                    if self.item_index > 0 {
-                        self.item_index -= 1;
-                        self.table_state.select(Some(self.item_index));
-                    }
+                Key::Up if self.item_index > 0 => {
+                    self.item_index -= 1;
+                    self.table_state.select(Some(self.item_index));
                 }
 
                 // Navigate by column
                 Key::Right => {
-                    if self.column_index < 11 {
-                        self.column_index += 1;
+                    let columns = self.visible_columns();
+                    if let Some(i) = columns.iter().position(|&c| c == self.column_index) {
+                        self.column_index = columns[(i + 1).min(columns.len() - 1)];
                     }
                 }
                 Key::Left => {
-                    if self.column_index > 0 {
-                        self.column_index -= 1;
+                    let columns = self.visible_columns();
+                    if let Some(i) = columns.iter().position(|&c| c == self.column_index) {
+                        self.column_index = columns[i.saturating_sub(1)];
                     }
                 }
-                // Filter by selected column
-                Key::Char('f') => {
-                    if self.is_usize_column(self.column_index)
-                        || self.is_string_column(self.column_index)
-                    {
-                        self.item_index = 0;
-                        self.table_state.select(Some(self.item_index));
-                        self.input_mode = InputMode::Filter;
-                        self.popup_open = !self.popup_open;
+                // Switch memory columns
+                Key::Char('m') => self.switch_memory_columns(),
+                // Show details of the selected process, the NUMA distribution follows with
+                // the next update
+                Key::Char('\n') => {
+                    let process_list = &self.process_list;
+                    self.details = process_list
+                        .processes
+                        .get(self.item_index)
+                        .map(|p| ProcessDetails::new(p, process_list));
+                    if let Some(details) = &self.details {
+                        self.details_pid
+                            .store(details.process.pid, AtomicOrdering::Relaxed);
                     }
                 }
+                // Filter by selected column
+                Key::Char('f') if self.is_filter_column(self.column_index) => {
+                    self.item_index = 0;
+                    self.table_state.select(Some(self.item_index));
+                    self.input_mode = InputMode::Filter;
+                    self.popup_open = !self.popup_open;
+                }
                 // Reset filter
                 Key::Char('r') => {
                     self.filter_index = None;
                     self.refresh_filter();
                 }
+                // Show the sockets of the selected process
+                Key::Char('c') => {
+                    self.connections_request = self
+                        .process_list
+                        .processes
+                        .get(self.item_index)
+                        .map(|p| p.pid);
+                }
                 // Kill process
                 Key::Char('k') => {
                     util::kill_process(self.process_list.processes[self.item_index].pid)
@@ -809,6 +1414,11 @@ impl ProcessesWidget {
                     self.input_mode = InputMode::Niceness;
                     self.popup_open = !self.popup_open;
                 }
+                // Change OOM score adjustment of process
+                Key::Char('o') => {
+                    self.input_mode = InputMode::OomScoreAdj;
+                    self.popup_open = !self.popup_open;
+                }
                 // Sort by current selected column
                 Key::Char('s') => {
                     if self.sort_index == self.column_index {
@@ -828,48 +1438,73 @@ impl ProcessesWidget {
                 }
                 // Input is finished
                 Key::Char('\n') => {
-                    let input_value = self.input.parse().unwrap_or_default();
-
                     // Update niceness
                     if self.input_mode == InputMode::Niceness {
+                        let input_value = self.input.parse().unwrap_or_default();
                         util::update_niceness(
                             self.process_list.processes[self.item_index].pid,
                             input_value,
                         );
+                    } else if self.input_mode == InputMode::OomScoreAdj {
+                        // Keep the popup open with the reason if the value isn't applied
+                        let pid = self
+                            .process_list
+                            .processes
+                            .get(self.item_index)
+                            .map(|p| p.pid);
+                        let result = match (self.input.parse::<i16>(), pid) {
+                            (Ok(value), Some(pid)) => util::update_oom_score_adj(pid, value),
+                            (Err(_), _) => Err(io::Error::new(ErrorKind::InvalidInput, "")),
+                            (_, None) => Ok(()),
+                        };
+                        if let Err(e) = result {
+                            self.input_error = Some(match e.kind() {
+                                ErrorKind::PermissionDenied => {
+                                    "Lowering requires CAP_SYS_RESOURCE".to_string()
+                                }
+                                ErrorKind::InvalidInput => "Not between -1000 and 1000".to_string(),
+                                _ => format!("Failed: {}", e),
+                            });
+                            self.input.clear();
+                            return;
+                        }
                     } else if self.input_mode == InputMode::Filter {
                         // Filter by selected column
                         self.filter_index = Some(self.column_index);
-                        match self.filter_index {
-                            Some(i) => {
-                                if self.is_usize_column(i) {
-                                    // Store value in usize field.
-                                    let input_value: usize = self.input.parse().unwrap_or_default();
-                                    self.filter_value_usize = input_value;
-                                } else if self.is_string_column(i) {
-                                    // Store value in string field.
-                                    let input_value: String =
-                                        self.input.parse().unwrap_or_default();
-                                    self.filter_value_str = input_value;
-                                }
+                        if let Some(i) = self.filter_index {
+                            if self.is_usize_column(i) || self.is_minimum_column(i) {
+                                // Store value in usize field.
+                                let input_value: usize = self.input.parse().unwrap_or_default();
+                                self.filter_value_usize = input_value;
+                            } else if self.is_string_column(i) {
+                                // Store value in string field.
+                                let input_value: String = self.input.parse().unwrap_or_default();
+                                self.filter_value_str = input_value;
                             }
-                            None => {}
                         }
                         self.refresh_filter();
                     }
                     // Clear buffer
                     self.input.clear();
+                    self.input_error = None;
                     // Close popup
                     self.popup_open = false;
                 }
                 // Cancel input
                 Key::Ctrl('x') => {
                     self.input.clear();
+                    self.input_error = None;
                     self.popup_open = false;
                 }
                 // Store pressed key in temporary buffer.
                 Key::Char(key) => {
                     if self.input_mode == InputMode::Filter {
                         self.input.push(key)
+                    } else if self.input_mode == InputMode::OomScoreAdj {
+                        // -1000 to 1000
+                        if self.input.len() < 5 {
+                            self.input.push(key)
+                        }
                     } else {
                         if self.input.len() < 3 {
                             self.input.push(key)
@@ -879,6 +1514,7 @@ impl ProcessesWidget {
                 // Close the popup.
                 Key::Esc => {
                     self.input.clear();
+                    self.input_error = None;
                     self.popup_open = false;
                 }
                 _ => {}
@@ -895,6 +1531,21 @@ impl ProcessesWidget {
         v == 3 || v == 6 || v == 7 || v == 11 || v == 4
     }
 
+    /// Returns whether the column is filtered by a minimum value, like the memory columns.
+    fn is_minimum_column(&self, v: usize) -> bool {
+        v == 10 || (12..=19).contains(&v)
+    }
+
+    /// Returns whether the process list can be filtered by the column.
+    fn is_filter_column(&self, v: usize) -> bool {
+        self.is_usize_column(v) || self.is_string_column(v) || self.is_minimum_column(v)
+    }
+
+    /// Returns the pid of the process whose sockets were requested, resets the request.
+    pub fn take_connections_request(&mut self) -> Option<usize> {
+        self.connections_request.take()
+    }
+
     /// Returns dynamic help text based on current widget state.
     pub fn get_help_text(&self) -> &str {
         let i = self.column_index;
@@ -902,7 +1553,7 @@ impl ProcessesWidget {
         match self.filter_index {
             Some(i) => {
                 // Check whether it is possible to filter by the current column
-                if self.is_string_column(i) || self.is_usize_column(i) {
+                if self.is_filter_column(i) {
                     ", f: filter, r: reset filter"
                 } else {
                     ", r: reset filter"
@@ -910,7 +1561,7 @@ impl ProcessesWidget {
             }
             None => {
                 // Check whether it is possible to filter by the current column
-                if self.is_string_column(i) || self.is_usize_column(i) {
+                if self.is_filter_column(i) {
                     ", f: filter"
                 } else {
                     ""
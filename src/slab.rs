@@ -0,0 +1,472 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::sync::{mpsc, OnceLock};
+use std::{io::BufRead, io::BufReader};
+use std::{thread, time, time::Instant};
+use termion::event::Key;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::util::{self, Sample};
+
+const PROC_SLABINFO: &str = "/proc/slabinfo";
+
+/// Returns the size of a memory page in bytes, slabs are allocated in pages.
+///
+/// The size is read once and falls back to 4096 if it can't be determined.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn page_size() -> u64 {
+    static PAGE_SIZE: OnceLock<u64> = OnceLock::new();
+    *PAGE_SIZE.get_or_init(|| match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as u64,
+        _ => 4096,
+    })
+}
+
+/// A row of /proc/slabinfo
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SlabRow {
+    pub name: String,
+    /// Number of objects in use
+    pub active_objs: u64,
+    /// Number of allocated objects
+    pub num_objs: u64,
+    /// Size of an object in bytes
+    pub obj_size: u64,
+    /// Number of allocated slabs
+    pub num_slabs: u64,
+    /// Number of pages per slab
+    pub pages_per_slab: u64,
+}
+
+impl SlabRow {
+    /// Returns the memory used by the slabs of the cache in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.num_slabs * self.pages_per_slab * page_size()
+    }
+}
+
+/// A slab cache with its growth since the previous measurement
+#[derive(Default, Debug, Clone)]
+pub struct SlabCache {
+    pub row: SlabRow,
+    /// Change of the total size in bytes per second
+    pub growth_rate: f64,
+}
+
+/// Slab caches sent by the data collection thread
+#[derive(Default, Debug, Clone)]
+pub struct SlabStats {
+    pub caches: Vec<SlabCache>,
+    /// Reason why /proc/slabinfo couldn't be read
+    pub error: Option<String>,
+}
+
+/// Parses the content of /proc/slabinfo (version 2.x).
+///
+/// Each line has the format "name <active_objs> <num_objs> <objsize> <objperslab>
+/// <pagesperslab> : tunables ... : slabdata <active_slabs> <num_slabs> <sharedavail>".
+/// The version line, the comment line and malformed lines are skipped.
+///
+/// See https://man7.org/linux/man-pages/man5/slabinfo.5.html
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn parse_slabinfo<R: BufRead>(reader: R) -> Vec<SlabRow> {
+    let mut rows = Vec::new();
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(x) => x,
+            Err(_) => break,
+        };
+        if line.starts_with("slabinfo") || line.starts_with('#') {
+            continue;
+        }
+
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        // name, 5 values, ":", "tunables", 3 values, ":", "slabdata", 3 values
+        if columns.len() < 16 {
+            continue;
+        }
+        let value = |i: usize| columns[i].parse().unwrap_or_default();
+
+        rows.push(SlabRow {
+            name: columns[0].to_string(),
+            active_objs: value(1),
+            num_objs: value(2),
+            obj_size: value(3),
+            pages_per_slab: value(5),
+            num_slabs: value(14),
+        });
+    }
+
+    rows
+}
+
+/// Reads /proc/slabinfo.
+///
+/// Returns a message for the user if the file can't be read, which is usually the case
+/// for non-root users.
+///
+/// # Panic
+///
+/// This function won't panic.
+fn read_slabinfo() -> Result<Vec<SlabRow>, String> {
+    match File::open(PROC_SLABINFO) {
+        Ok(file) => Ok(parse_slabinfo(BufReader::new(file))),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => Err(format!(
+            "Permission denied: {} is only readable by root. Run nmtop as root to see the slab caches.",
+            PROC_SLABINFO
+        )),
+        Err(e) => Err(format!("{} is not available: {}", PROC_SLABINFO, e)),
+    }
+}
+
+/// Calculates the growth rates of the slab caches based on two measurements.
+///
+/// Caches without previous measurement get a growth rate of zero.
+///
+/// # Arguments
+///
+/// * 'previous' - previous total sizes mapped by cache name
+/// * 'current' - current rows
+/// * 'elapsed' - elapsed time between both measurements in seconds
+///
+/// # Panic
+///
+/// This function won't panic.
+fn calculate_growth(
+    previous: &HashMap<String, u64>,
+    current: Vec<SlabRow>,
+    elapsed: f64,
+) -> Vec<SlabCache> {
+    current
+        .into_iter()
+        .map(|row| {
+            let growth_rate = match previous.get(&row.name) {
+                Some(old) if elapsed > 0.0 => (row.total_size() as f64 - *old as f64) / elapsed,
+                _ => 0.0,
+            };
+            SlabCache { row, growth_rate }
+        })
+        .collect()
+}
+
+/// Initializes a thread to collect and send the slab caches every two seconds.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn init_data_collection_thread() -> mpsc::Receiver<Sample<SlabStats>> {
+    let (tx, rx) = mpsc::channel();
+    let dur = time::Duration::from_millis(2000);
+
+    let mut previous: HashMap<String, u64> = HashMap::new();
+    let mut previous_time = Instant::now();
+
+    // Thread for the data collection
+    thread::spawn(move || loop {
+        let rows = Sample {
+            time: Instant::now(),
+            data: read_slabinfo(),
+        };
+        let elapsed = rows.elapsed_since(previous_time);
+        previous_time = rows.time;
+
+        let stats = match rows.data {
+            Ok(rows) => {
+                let caches = calculate_growth(&previous, rows, elapsed);
+                previous = caches
+                    .iter()
+                    .map(|c| (c.row.name.clone(), c.row.total_size()))
+                    .collect();
+                SlabStats {
+                    caches,
+                    error: None,
+                }
+            }
+            Err(e) => SlabStats {
+                caches: vec![],
+                error: Some(e),
+            },
+        };
+
+        let _ = tx.send(Sample {
+            time: rows.time,
+            data: stats,
+        });
+
+        thread::sleep(dur);
+    });
+
+    rx
+}
+
+/// Names of the table columns
+const COLUMN_NAMES: [&str; 8] = [
+    "Cache", "Size", "Growth/s", "Active", "Objects", "Use", "ObjSize", "Slabs",
+];
+
+/// Stores the state data of the widget.
+pub struct SlabWidget {
+    /// Used for the selection of the current row.
+    table_state: TableState,
+    /// Index of the current selected item.
+    item_index: usize,
+    /// Index of column to sort by.
+    sort_index: usize,
+    /// Index of current selected column.
+    column_index: usize,
+    /// Sort the column descending or ascending.
+    sort_descending: bool,
+    /// Stores data inside the table.
+    stats: SlabStats,
+    /// Used to receive data from the thread which reads the slab caches.
+    dc_thread: mpsc::Receiver<Sample<SlabStats>>,
+}
+
+impl SlabWidget {
+    /// Creates new slab widget with default values, sorted by the total size.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn new() -> Self {
+        let mut a = Self {
+            table_state: TableState::default(),
+            item_index: 0,
+            sort_index: 1,
+            column_index: 1,
+            sort_descending: true,
+            stats: Default::default(),
+            dc_thread: init_data_collection_thread(),
+        };
+        a.table_state.select(Some(0));
+        a
+    }
+
+    /// Sorts the slab caches ascending or descending by the current selected column.
+    fn sort(&mut self) {
+        let sort_index = self.sort_index;
+        let sort_descending = self.sort_descending;
+        self.stats.caches.sort_by(|a, b| {
+            let (a_row, b_row) = (&a.row, &b.row);
+            let s = match sort_index {
+                0 => a_row.name.cmp(&b_row.name),
+                1 => a_row.total_size().cmp(&b_row.total_size()),
+                2 => a
+                    .growth_rate
+                    .partial_cmp(&b.growth_rate)
+                    .unwrap_or(Ordering::Equal),
+                3 => a_row.active_objs.cmp(&b_row.active_objs),
+                4 => a_row.num_objs.cmp(&b_row.num_objs),
+                5 => get_usage(a_row)
+                    .partial_cmp(&get_usage(b_row))
+                    .unwrap_or(Ordering::Equal),
+                6 => a_row.obj_size.cmp(&b_row.obj_size),
+                7 => a_row.num_slabs.cmp(&b_row.num_slabs),
+                _ => Ordering::Equal,
+            };
+            if sort_descending {
+                Ordering::reverse(s)
+            } else {
+                s
+            }
+        });
+    }
+
+    /// Retrieves information from the update thread and store the new data in the widget.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn update(&mut self) {
+        // Recv data from the data collector thread
+        if let Ok(sample) = self.dc_thread.try_recv() {
+            self.stats = sample.data;
+            self.sort();
+
+            // Keep the selection inside the table
+            if self.item_index >= self.stats.caches.len() {
+                self.item_index = self.stats.caches.len().saturating_sub(1);
+                self.table_state.select(Some(self.item_index));
+            }
+        }
+    }
+
+    /// Draws the slab caches and a summary line in a given Rect.
+    ///
+    /// If /proc/slabinfo can't be read, the reason is shown instead.
+    ///
+    /// # Arguments
+    ///
+    /// * 'f' - A refrence to the terminal interface for rendering
+    /// * 'rect' - A rectangle used to hint the area the widget gets rendered in
+    /// * 'block' - A Box with borders and title which contains the drawn widget
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn draw<B: Backend>(&mut self, f: &mut Frame<B>, rect: Rect, block: Block) {
+        if let Some(error) = &self.stats.error {
+            let paragraph = Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Yellow))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .block(block);
+            f.render_widget(paragraph, rect);
+            return;
+        }
+
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+            .split(inner);
+
+        // Sum up all caches like slabtop
+        let caches = &self.stats.caches;
+        let total: u64 = caches.iter().map(|c| c.row.total_size()).sum();
+        let active: u64 = caches
+            .iter()
+            .map(|c| c.row.active_objs * c.row.obj_size)
+            .sum();
+        let growth: f64 = caches.iter().map(|c| c.growth_rate).sum();
+        let summary = Spans::from(vec![
+            Span::styled("Total: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(
+                "{} in {} caches, {} of objects in use, growth {}/s",
+                util::to_humanreadable(total as usize),
+                caches.len(),
+                util::to_humanreadable(active as usize),
+                format_growth(growth)
+            )),
+        ]);
+        f.render_widget(Paragraph::new(summary), chunks[0]);
+
+        // Create styles
+        let selected_style = Style::default()
+            .fg(Color::White)
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED);
+        let header_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+
+        // Create new header row in table
+        let header_cells = COLUMN_NAMES.iter().enumerate().map(|(i, h)| {
+            if i == self.column_index {
+                Cell::from(*h).style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+            } else {
+                Cell::from(*h)
+            }
+        });
+        let header = Row::new(header_cells).style(header_style).height(1);
+
+        // Populate rows of table
+        let rows = caches.iter().map(|c| {
+            let growth_style = match c.growth_rate {
+                x if x > 0.0 => Style::default().fg(Color::Yellow),
+                x if x < 0.0 => Style::default().fg(Color::Green),
+                _ => Style::default(),
+            };
+            Row::new(vec![
+                Cell::from(c.row.name.clone()),
+                Cell::from(format!(
+                    "{: >10}",
+                    util::to_humanreadable(c.row.total_size() as usize)
+                )),
+                Cell::from(format!("{: >11}", format_growth(c.growth_rate))).style(growth_style),
+                Cell::from(format!("{: >10}", c.row.active_objs)),
+                Cell::from(format!("{: >10}", c.row.num_objs)),
+                Cell::from(format!("{: >5.0}%", get_usage(&c.row))),
+                Cell::from(format!("{: >8}", c.row.obj_size)),
+                Cell::from(format!("{: >8}", c.row.num_slabs)),
+            ])
+            .height(1)
+        });
+
+        // Create new table
+        let table = Table::new(rows)
+            .header(header)
+            .highlight_style(selected_style)
+            .widths(&[
+                Constraint::Length(28),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ]);
+        f.render_stateful_widget(table, chunks[1], &mut self.table_state);
+    }
+
+    /// Handles keyboard input
+    ///
+    /// # Arguments
+    ///
+    /// * 'key' - The pressed key.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    pub fn handle_input(&mut self, key: Key) {
+        match key {
+            // Navigate by row
+            Key::Down if self.item_index + 1 < self.stats.caches.len() => {
+                self.item_index += 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            Key::Up if self.item_index > 0 => {
+                self.item_index -= 1;
+                self.table_state.select(Some(self.item_index));
+            }
+            // Navigate by column
+            Key::Right if self.column_index + 1 < COLUMN_NAMES.len() => {
+                self.column_index += 1;
+            }
+            Key::Left if self.column_index > 0 => {
+                self.column_index -= 1;
+            }
+            // Sort by current selected column
+            Key::Char('s') => {
+                if self.sort_index == self.column_index {
+                    self.sort_descending = !self.sort_descending;
+                }
+
+                self.sort_index = self.column_index;
+                self.sort();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the share of the objects in use in percent.
+fn get_usage(row: &SlabRow) -> f64 {
+    match row.num_objs {
+        0 => 0.0,
+        n => row.active_objs as f64 / n as f64 * 100.0,
+    }
+}
+
+/// Formats a growth rate in bytes per second with a sign, e.g. "+1.2 MiB".
+fn format_growth(rate: f64) -> String {
+    let sign = if rate < 0.0 { "-" } else { "+" };
+    sign.to_string() + &util::to_humanreadable(rate.abs() as usize)
+}
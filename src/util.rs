@@ -1,7 +1,7 @@
+use std::fs;
 use std::io;
 use std::process::Command;
 use std::sync::mpsc;
-use std::time::{SystemTime, UNIX_EPOCH};
 use std::{thread, time::Instant};
 use termion::event::Key;
 use termion::input::TermRead;
@@ -45,7 +45,7 @@ impl InputHandler {
                 }
             }
         });
-        InputHandler { rx: rx }
+        InputHandler { rx }
     }
 
     /// Tries to fetch new event from channel
@@ -58,6 +58,43 @@ impl InputHandler {
     }
 }
 
+/// A measurement of a data collection thread with the monotonic time it was taken at.
+///
+/// Rates are calculated by dividing the difference of two samples by the actual time
+/// between them, so delayed or missed samples don't distort the result.
+pub struct Sample<T> {
+    pub time: Instant,
+    pub data: T,
+}
+
+impl<T> Sample<T> {
+    /// Returns the seconds elapsed between a previous sample and this one.
+    pub fn elapsed_since(&self, previous: Instant) -> f64 {
+        self.time.duration_since(previous).as_secs_f64()
+    }
+}
+
+/// Returns the change per second of a counter between two samples.
+///
+/// Counters which wrapped or were reset, e.g. by removing and adding a device again,
+/// result in 0, as well as samples without elapsed time.
+///
+/// # Arguments
+///
+/// * `previous` - counter value of the previous sample
+/// * `current` - counter value of the current sample
+/// * `elapsed` - seconds between both samples, see Sample::elapsed_since
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn rate(previous: u64, current: u64, elapsed: f64) -> f64 {
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    current.saturating_sub(previous) as f64 / elapsed
+}
+
 const SIZES: [&str; 5] = [" B", " KiB", " MiB", " GiB", " TiB"];
 
 /// Convert bytes to human readable format.
@@ -76,7 +113,7 @@ pub fn to_humanreadable(bytes: usize) -> String {
     let mut size = bytes as f64;
 
     while size > 1000.0 {
-        size = size / 1024.0;
+        size /= 1024.0;
         count += 1;
     }
 
@@ -85,6 +122,30 @@ pub fn to_humanreadable(bytes: usize) -> String {
     size_string + SIZES[count]
 }
 
+const COUNT_SUFFIXES: [&str; 5] = ["", "k", "M", "G", "T"];
+
+/// Convert a count (e.g. events per second) to a short human readable format.
+/// Values are displayed with a decimal suffix like k, M or G.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn to_humanreadable_count(value: f64) -> String {
+    let mut count = 0;
+    let mut size = value;
+
+    while size >= 1000.0 && count < COUNT_SUFFIXES.len() - 1 {
+        size /= 1000.0;
+        count += 1;
+    }
+
+    if count == 0 {
+        return format!("{:.0}", size);
+    }
+
+    format!("{:.1}{}", size, COUNT_SUFFIXES[count])
+}
+
 /// Send a kill signal to a process selected by the pid.
 ///
 /// # Arguments
@@ -97,7 +158,7 @@ pub fn to_humanreadable(bytes: usize) -> String {
 pub fn kill_process(pid: usize) {
     let pid_string = &pid.to_string();
     Command::new("kill")
-        .args(&["-9", pid_string])
+        .args(["-9", pid_string])
         .output()
         .expect("failed to kill process");
 }
@@ -117,28 +178,38 @@ pub fn kill_process(pid: usize) {
 /// This function won't panic.
 pub fn update_niceness(pid: usize, new_niceness: i8) {
     // niceness is measured between -20 and 19
-    if new_niceness >= -20 && new_niceness <= 19 {
+    if (-20..=19).contains(&new_niceness) {
         let pid_string = &pid.to_string();
         let niceness_string = &new_niceness.to_string();
         Command::new("renice")
-            .args(&["-n", niceness_string, "-p", pid_string])
+            .args(["-n", niceness_string, "-p", pid_string])
             .output()
             .expect("failed adjust niceness");
     }
 }
 
-/// Get the millis since Day 0 (01.01.1970)
+/// Update the OOM score adjustment of a process
+///
+/// Writes the new value to '/proc/[pid]/oom_score_adj'. Lowering the value requires
+/// the CAP_SYS_RESOURCE capability.
+///
+/// # Arguments
+///
+/// * `pid` - the process id of the process
+/// * `new_adj` - the new adjustment between -1000 (never kill) and 1000 (kill first)
+///
+/// # Errors
 ///
-/// see: answer by shepmaster https://stackoverflow.com/questions/26593387/how-can-i-get-the-current-time-in-milliseconds#44378174
+/// Returns an error if the value is out of range or the kernel rejects it.
 ///
 /// # Panic
 ///
 /// This function won't panic.
-pub fn get_millis() -> usize {
-    let tmp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards");
-    tmp.as_secs() as usize * 1000 + tmp.subsec_nanos() as usize / 1_000_000 as usize
+pub fn update_oom_score_adj(pid: usize, new_adj: i16) -> io::Result<()> {
+    if !(-1000..=1000).contains(&new_adj) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "out of range"));
+    }
+    fs::write(format!("/proc/{}/oom_score_adj", pid), new_adj.to_string())
 }
 
 /// Get a color based on a scalar.
@@ -172,3 +243,35 @@ pub fn get_color_by_scalar(i: usize) -> Color {
     }
     color
 }
+
+/// Get a color based on a utilization in percent.
+///
+/// Low values are blue, medium values green/yellow and high values red.
+///
+/// # Arguments
+///
+/// * 'percent' - utilization between 0 and 100
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn get_color_by_utilization(percent: f64) -> Color {
+    let p = percent.clamp(0.0, 100.0) / 100.0;
+    if p < 0.25 {
+        // dark blue to cyan
+        let x = p / 0.25;
+        Color::Rgb(0, (x * 180.0) as u8, 90 + (x * 90.0) as u8)
+    } else if p < 0.5 {
+        // cyan to green
+        let x = (p - 0.25) / 0.25;
+        Color::Rgb(0, 180 + (x * 75.0) as u8, 180 - (x * 180.0) as u8)
+    } else if p < 0.75 {
+        // green to yellow
+        let x = (p - 0.5) / 0.25;
+        Color::Rgb((x * 255.0) as u8, 255, 0)
+    } else {
+        // yellow to red
+        let x = (p - 0.75) / 0.25;
+        Color::Rgb(255, 255 - (x * 255.0) as u8, 0)
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::{self, read_dir};
+
+const SYS_BLOCK: &str = "/sys/block";
+const ZSWAP_PARAMETERS: &str = "/sys/module/zswap/parameters";
+const ZSWAP_DEBUGFS: &str = "/sys/kernel/debug/zswap";
+
+/// Statistics of a zram device, read from /sys/block/zram*/mm_stat
+///
+/// See https://www.kernel.org/doc/html/latest/admin-guide/blockdev/zram.html
+#[derive(Default, Debug, Clone)]
+pub struct ZramDevice {
+    /// Name of the device, e.g. "zram0"
+    pub name: String,
+    /// Active compression algorithm
+    pub algorithm: String,
+    /// Size of the device in bytes
+    pub disk_size: u64,
+    /// Uncompressed size of the stored data in bytes
+    pub orig_data_size: u64,
+    /// Compressed size of the stored data in bytes
+    pub compr_data_size: u64,
+    /// Memory used by the device including allocator overhead in bytes
+    pub mem_used_total: u64,
+}
+
+impl ZramDevice {
+    /// Returns the ratio of the original to the compressed size, 0 if the device is empty.
+    pub fn compression_ratio(&self) -> f64 {
+        match self.compr_data_size {
+            0 => 0.0,
+            compressed => self.orig_data_size as f64 / compressed as f64,
+        }
+    }
+}
+
+/// State and pool usage of the zswap compressed swap cache
+///
+/// See https://www.kernel.org/doc/html/latest/admin-guide/mm/zswap.html
+#[derive(Default, Debug, Clone)]
+pub struct ZswapStats {
+    pub enabled: bool,
+    /// Compression algorithm of the pool
+    pub compressor: String,
+    /// Maximum size of the pool in percent of the total memory
+    pub max_pool_percent: u64,
+    /// Uncompressed size of the stored pages in KiB
+    pub orig_data_size: u64,
+    /// Size of the compressed pool in KiB
+    pub pool_size: u64,
+}
+
+impl ZswapStats {
+    /// Returns the ratio of the original to the compressed size, 0 if the pool is empty.
+    pub fn compression_ratio(&self) -> f64 {
+        match self.pool_size {
+            0 => 0.0,
+            compressed => self.orig_data_size as f64 / compressed as f64,
+        }
+    }
+}
+
+/// Reads the value of a sysfs attribute, trimmed of the trailing newline.
+fn read_attribute(path: &str) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Returns the selected entry of a sysfs list like "lzo [lz4] zstd".
+///
+/// Lists without a selected entry are returned unchanged.
+fn get_selected(list: &str) -> String {
+    list.split_whitespace()
+        .find_map(|e| e.strip_prefix('[').and_then(|e| e.strip_suffix(']')))
+        .unwrap_or(list)
+        .to_string()
+}
+
+/// Reads the statistics of all initialized zram devices.
+///
+/// The mm_stat file contains the space separated values orig_data_size,
+/// compr_data_size, mem_used_total, ... in bytes. Devices without mm_stat
+/// (old kernels) or without a disk size (not initialized) are skipped.
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_zram_devices() -> Vec<ZramDevice> {
+    let dirs = match read_dir(SYS_BLOCK) {
+        Ok(x) => x,
+        Err(_) => return vec![],
+    };
+
+    let mut devices: Vec<ZramDevice> = dirs
+        .filter_map(|d| d.ok())
+        .filter_map(|d| d.file_name().into_string().ok())
+        .filter(|name| name.starts_with("zram"))
+        .filter_map(|name| {
+            let path = format!("{}/{}", SYS_BLOCK, name);
+            let mm_stat = read_attribute(&format!("{}/mm_stat", path))?;
+            let values: Vec<u64> = mm_stat
+                .split_whitespace()
+                .map(|v| v.parse().unwrap_or_default())
+                .collect();
+            let disk_size = read_attribute(&format!("{}/disksize", path))?
+                .parse()
+                .unwrap_or_default();
+            if disk_size == 0 || values.len() < 3 {
+                return None;
+            }
+
+            Some(ZramDevice {
+                algorithm: get_selected(
+                    &read_attribute(&format!("{}/comp_algorithm", path)).unwrap_or_default(),
+                ),
+                name,
+                disk_size,
+                orig_data_size: values[0],
+                compr_data_size: values[1],
+                mem_used_total: values[2],
+            })
+        })
+        .collect();
+    devices.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+/// Reads the zswap state and pool usage.
+///
+/// Returns None if the kernel has no zswap support. The pool usage is taken from
+/// the Zswap and Zswapped fields of /proc/meminfo, older kernels only provide it
+/// in debugfs which usually requires root privileges.
+///
+/// # Arguments
+///
+/// * 'meminfo' - The fields of /proc/meminfo
+///
+/// # Panic
+///
+/// This function won't panic.
+pub fn read_zswap(meminfo: &HashMap<String, u64>) -> Option<ZswapStats> {
+    let enabled = read_attribute(&format!("{}/enabled", ZSWAP_PARAMETERS))?;
+
+    let mut stats = ZswapStats {
+        enabled: enabled == "Y",
+        compressor: read_attribute(&format!("{}/compressor", ZSWAP_PARAMETERS)).unwrap_or_default(),
+        max_pool_percent: read_attribute(&format!("{}/max_pool_percent", ZSWAP_PARAMETERS))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    if let (Some(pool), Some(orig)) = (meminfo.get("Zswap"), meminfo.get("Zswapped")) {
+        stats.pool_size = *pool;
+        stats.orig_data_size = *orig;
+    } else {
+        let debugfs = |name: &str| -> u64 {
+            read_attribute(&format!("{}/{}", ZSWAP_DEBUGFS, name))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default()
+        };
+        // stored_pages counts 4 KiB pages, pool_total_size is in bytes
+        stats.pool_size = debugfs("pool_total_size") / 1024;
+        stats.orig_data_size = debugfs("stored_pages") * 4;
+    }
+
+    Some(stats)
+}